@@ -3,8 +3,8 @@ use cosmwasm_std::entry_point;
 use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
-use crate::state::Contract;
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use crate::state::{Contract, Extension};
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -12,8 +12,8 @@ pub fn instantiate(
     env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
-) -> StdResult<Response> {
-    let contract = Contract::get_contract();
+) -> Result<Response, ContractError> {
+    let contract = Contract::<Extension>::get_contract();
 
     contract.instantiate(deps, env, info, msg)
 }
@@ -25,22 +25,29 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
-    let contract = Contract::get_contract();
+    let contract = Contract::<Extension>::get_contract();
 
     contract.execute(deps, env, info, msg)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    let contract = Contract::get_contract();
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    let contract = Contract::<Extension>::get_contract();
+
+    contract.query(deps, env, msg)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let contract = Contract::<Extension>::get_contract();
 
-    contract.query(deps, msg)
+    contract.migrate(deps, env, msg)
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        response::ContractInfoResponse,
+        state::CollectionInfo,
         utils::test_utils::{initialize_contract, MINTER, NAME, OWNER, SYMBOL},
     };
 
@@ -60,9 +67,12 @@ mod tests {
         );
         assert_eq!(
             contract.contract_info.load(&deps.storage).unwrap(),
-            ContractInfoResponse {
+            CollectionInfo {
                 name: NAME.to_string(),
-                symbol: SYMBOL.to_string()
+                symbol: SYMBOL.to_string(),
+                auto_token_id: false,
+                max_supply: None,
+                soulbound: false
             }
         )
     }
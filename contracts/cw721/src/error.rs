@@ -1,14 +1,124 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{Coin, StdError};
 use thiserror::Error;
 
+use crate::receiver::ReceiverError;
+
 #[derive(Error, Debug)]
 pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Receiver(#[from] ReceiverError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
     #[error("TokenAlreadyExistsError")]
     TokenAlreadyExistsError {},
+
+    #[error("Invalid batch size, at most {max} tokens")]
+    InvalidBatchSize { max: u32 },
+
+    #[error("Duplicate token id: {token_id}")]
+    DuplicateTokenId { token_id: String },
+
+    #[error("Invalid token id: {reason}")]
+    InvalidTokenId { reason: String },
+
+    #[error("MissingTokenId")]
+    MissingTokenId {},
+
+    #[error("UnexpectedTokenId")]
+    UnexpectedTokenId {},
+
+    #[error("MaxSupplyReached")]
+    MaxSupplyReached {},
+
+    #[error("CannotRaiseMaxSupply")]
+    CannotRaiseMaxSupply {},
+
+    #[error("InvalidMaxSupply")]
+    InvalidMaxSupply {},
+
+    #[error("InvalidTokenIdRules")]
+    InvalidTokenIdRules {},
+
+    #[error("MetadataFrozen")]
+    MetadataFrozen {},
+
+    #[error("NonTransferable")]
+    NonTransferable {},
+
+    #[error("Paused")]
+    Paused {},
+
+    #[error("InvalidSaleConfig")]
+    InvalidSaleConfig {},
+
+    #[error("SaleNotActive")]
+    SaleNotActive {},
+
+    #[error("SaleNotStarted")]
+    SaleNotStarted {},
+
+    #[error("Invalid mint quantity, at most {max} tokens")]
+    InvalidMintQuantity { max: u32 },
+
+    #[error("Invalid payment, expected {expected}")]
+    InvalidPayment { expected: Coin },
+
+    #[error("AllowlistNotActive")]
+    AllowlistNotActive {},
+
+    #[error("InvalidMerkleProof")]
+    InvalidMerkleProof {},
+
+    #[error("InvalidMerkleRoot")]
+    InvalidMerkleRoot {},
+
+    #[error("AutoTokenIdRequired")]
+    AutoTokenIdRequired {},
+
+    #[error("Allocation exceeded, {remaining} left")]
+    AllocationExceeded { remaining: u32 },
+
+    #[error("InvalidPhases")]
+    InvalidPhases {},
+
+    #[error("PhasesLocked")]
+    PhasesLocked {},
+
+    #[error("NoActivePhase")]
+    NoActivePhase {},
+
+    #[error("InsufficientBalance")]
+    InsufficientBalance {},
+
+    #[error("CannotRevokeLastAdmin")]
+    CannotRevokeLastAdmin {},
+
+    #[error("CannotRevokeOwnerAdmin")]
+    CannotRevokeOwnerAdmin {},
+
+    #[error("Expired")]
+    Expired {},
+
+    #[error("InvalidRoyaltyShare")]
+    InvalidRoyaltyShare {},
+
+    #[error("NoPendingOwner")]
+    NoPendingOwner {},
+
+    #[error("NotPendingOwner")]
+    NotPendingOwner {},
+
+    #[error("TransferExpired")]
+    TransferExpired {},
+
+    #[error("Wrong contract, expected {expected}, found {found}")]
+    WrongContract { expected: String, found: String },
+
+    #[error("Cannot downgrade from {previous} to {current}")]
+    CannotDowngrade { previous: String, current: String },
 }
@@ -1,34 +1,87 @@
-use cosmwasm_std::{from_binary, Binary, DepsMut, Env, MessageInfo, Response, StdResult};
+use std::collections::BTreeSet;
+
+use cosmwasm_std::{
+    to_binary, Addr, BankMsg, Binary, BlockInfo, Coin, Decimal, Deps, DepsMut, Env, Event,
+    HexBinary, MessageInfo, Order, Response, StdError, StdResult, Storage, Timestamp,
+};
 use cw2::set_contract_version;
 use cw721::Cw721ReceiveMsg;
+use cw_events::ActionEvent;
+use cw_utils::Expiration;
+use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{
-    msg::{ExecuteMsg, InstantiateMsg, TokenMsg},
-    response::ContractInfoResponse,
-    state::{Contract, Token, CONTRACT_NAME, CONTRACT_VERSION},
+    merkle::{leaf_hash, verify_proof},
+    msg::{
+        Cw721BatchReceiveMsg, ExecuteMsg, InstantiateMsg, OwnershipAction, RoyaltyMsg, TokenMsg,
+    },
+    receiver::{Cw721Receiver, ReceivedNft},
+    response::{MintBatchResponse, MintResponse},
+    state::{
+        Approval, CollectionInfo, Contract, Eligibility, MintPhase, PauseScope, PendingOwner,
+        ProvenanceKind, Role, Royalty, SaleConfig, Token, CONTRACT_NAME, CONTRACT_VERSION,
+        DEFAULT_PROVENANCE_LIMIT,
+    },
     ContractError,
 };
 
-impl<'a> Contract<'a> {
+/// Upper bound for the number of tokens handled by one batch message
+pub const MAX_BATCH_SIZE: usize = 100;
+
+impl<'a, T> Contract<'a, T>
+where
+    T: Serialize + DeserializeOwned + Clone + Default,
+{
     pub fn instantiate(
         &self,
         deps: DepsMut,
         _env: Env,
         info: MessageInfo,
         msg: InstantiateMsg,
-    ) -> StdResult<Response> {
+    ) -> Result<Response, ContractError> {
         set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
-        let contract_info = ContractInfoResponse {
+        if msg.max_supply == Some(0) {
+            return Err(ContractError::InvalidMaxSupply {});
+        }
+        let contract_info = CollectionInfo {
             name: msg.name,
             symbol: msg.symbol,
+            auto_token_id: msg.auto_token_id,
+            max_supply: msg.max_supply,
+            soulbound: msg.soulbound,
         };
         let owner = info.sender;
         let minter = deps.api.addr_validate(&msg.minter)?;
 
         self.minter.save(deps.storage, &minter)?;
         self.owner.save(deps.storage, &owner)?;
+        for role in Role::OWNER_ROLES {
+            self.save_role(deps.storage, role, &owner)?;
+        }
+        for role in Role::MINTER_ROLES {
+            self.save_role(deps.storage, role, &minter)?;
+        }
         self.contract_info.save(deps.storage, &contract_info)?;
+        self.token_count.save(deps.storage, &0)?;
+        self.provenance_limit.save(
+            deps.storage,
+            &msg.provenance_limit.unwrap_or(DEFAULT_PROVENANCE_LIMIT),
+        )?;
+        let token_id_rules = msg.token_id_rules.unwrap_or_default();
+        if token_id_rules.max_length == 0 {
+            return Err(ContractError::InvalidTokenIdRules {});
+        }
+        self.token_id_rules.save(deps.storage, &token_id_rules)?;
+        if let Some(sale) = msg.sale {
+            check_sale_config(&contract_info, &sale)?;
+            self.sale.save(deps.storage, &sale)?;
+        }
+
+        if let Some(royalty) = msg.royalty {
+            let royalty = self.validate_royalty(deps.as_ref(), royalty)?;
+            self.default_royalty.save(deps.storage, &royalty)?;
+        }
 
         Ok(Response::default())
     }
@@ -36,235 +89,3804 @@ impl<'a> Contract<'a> {
     pub fn execute(
         &self,
         deps: DepsMut,
-        _env: Env,
+        env: Env,
         info: MessageInfo,
-        msg: ExecuteMsg,
+        msg: ExecuteMsg<T>,
     ) -> Result<Response, ContractError> {
+        self.check_not_paused(deps.as_ref(), &msg)?;
+
         match msg {
-            ExecuteMsg::Mint { token } => self.mint(deps, info, token.clone()),
+            ExecuteMsg::Mint { token } => self.mint(deps, env, info, token),
+            ExecuteMsg::MintBatch { tokens } => self.mint_batch(deps, env, info, tokens),
             ExecuteMsg::SendNft {
                 token_id,
                 contract,
                 msg,
-            } => self.send_nft(deps, info, token_id, contract, msg),
-            ExecuteMsg::TransferNft { token_id, to } => self.transfer_nft(deps, info, token_id, to),
-            ExecuteMsg::ReceiveNft(msg) => self.receive_nft(msg),
+            } => self.send_nft(deps, env, info, token_id, contract, msg),
+            ExecuteMsg::TransferNft { token_id, to } => {
+                self.transfer_nft(deps, env, info, token_id, to)
+            }
+            ExecuteMsg::BatchTransferNft { token_ids, to } => {
+                self.batch_transfer_nft(deps, env, info, token_ids, to)
+            }
+            ExecuteMsg::BatchSendNft {
+                token_ids,
+                contract,
+                msg,
+            } => self.batch_send_nft(deps, env, info, token_ids, contract, msg),
+            ExecuteMsg::SetBatchReceive { enabled } => self.set_batch_receive(deps, info, enabled),
+            ExecuteMsg::ReceiveNft(msg) => self.receive_nft(deps, env, info, msg),
+            ExecuteMsg::Approve {
+                spender,
+                token_id,
+                expires,
+            } => self.approve(deps, env, info, spender, token_id, expires),
+            ExecuteMsg::Revoke { spender, token_id } => {
+                self.revoke(deps, env, info, spender, token_id)
+            }
+            ExecuteMsg::ApproveAll { operator, expires } => {
+                self.approve_all(deps, env, info, operator, expires)
+            }
+            ExecuteMsg::RevokeAll { operator } => self.revoke_all(deps, env, info, operator),
+            ExecuteMsg::Burn { token_id } => self.burn(deps, env, info, token_id),
+            ExecuteMsg::UpdateTokenUri {
+                token_id,
+                token_uri,
+            } => self.update_token_uri(deps, info, token_id, token_uri),
+            ExecuteMsg::FreezeToken { token_id } => self.freeze_token(deps, info, token_id),
+            ExecuteMsg::FreezeMetadata {} => self.freeze_metadata(deps, info),
+            ExecuteMsg::UpdateOwnership(action) => self.update_ownership(deps, env, info, action),
+            ExecuteMsg::UpdateMinter { minter } => self.update_minter(deps, info, minter),
+            ExecuteMsg::GrantRole { role, address } => self.grant_role(deps, info, role, address),
+            ExecuteMsg::RevokeRole { role, address } => self.revoke_role(deps, info, role, address),
+            ExecuteMsg::UpdateMaxSupply { max_supply } => {
+                self.update_max_supply(deps, info, max_supply)
+            }
+            ExecuteMsg::UpdateSale { sale } => self.update_sale(deps, info, sale),
+            ExecuteMsg::PublicMint { owner, quantity } => {
+                self.public_mint(deps, env, info, owner, quantity)
+            }
+            ExecuteMsg::SetMerkleRoot { root } => self.set_merkle_root(deps, info, root),
+            ExecuteMsg::AllowlistMint {
+                proof,
+                allocation,
+                quantity,
+            } => self.allowlist_mint(deps, env, info, proof, allocation, quantity),
+            ExecuteMsg::UpdatePhases { phases } => self.update_phases(deps, env, info, phases),
+            ExecuteMsg::PhaseMint {
+                quantity,
+                proof,
+                allocation,
+            } => self.phase_mint(deps, env, info, quantity, proof, allocation),
+            ExecuteMsg::Withdraw { recipient, amount } => {
+                self.withdraw(deps, env, info, recipient, amount)
+            }
+            ExecuteMsg::Pause { scope } => self.pause(deps, info, scope),
+            ExecuteMsg::Unpause {} => self.unpause(deps, info),
         }
     }
 }
 
-impl<'a> Contract<'a> {
-    pub fn mint(
+impl<'a, T> Contract<'a, T>
+where
+    T: Serialize + DeserializeOwned + Clone + Default,
+{
+    /// Mints to anyone who pays for the tokens
+    pub fn public_mint(
         &self,
         deps: DepsMut,
+        env: Env,
         info: MessageInfo,
-        msg: TokenMsg,
+        owner: String,
+        quantity: Option<u32>,
     ) -> Result<Response, ContractError> {
-        if info.sender.clone() != self.minter.load(deps.storage)? {
-            return Err(ContractError::Unauthorized {});
+        let sale = self
+            .sale
+            .may_load(deps.storage)?
+            .ok_or(ContractError::SaleNotActive {})?;
+        if env.block.time < sale.start_time {
+            return Err(ContractError::SaleNotStarted {});
         }
 
-        let token = Token {
-            owner: deps.api.addr_validate(&msg.owner)?,
-            token_id: msg.token_id.clone(),
-            token_uri: msg.token_uri.clone(),
+        let quantity = quantity.unwrap_or(1);
+        if quantity == 0 || quantity > sale.per_tx_limit {
+            return Err(ContractError::InvalidMintQuantity {
+                max: sale.per_tx_limit,
+            });
+        }
+        let total = Coin {
+            denom: sale.price.denom,
+            amount: sale
+                .price
+                .amount
+                .checked_mul(quantity.into())
+                .map_err(StdError::from)?,
         };
+        check_payment(&info, &total)?;
+        self.check_supply(deps.as_ref(), quantity.into())?;
 
-        self.tokens
-            .update(deps.storage, &msg.token_id, |old| match old {
-                Some(_) => Err(ContractError::TokenAlreadyExistsError {}),
-                None => Ok(token),
-            })?;
+        let owner = deps.api.addr_validate(&owner)?;
+        let (token_ids, events) =
+            self.mint_sequential(deps.storage, &env.block, &info.sender, &owner, quantity)?;
+
+        Ok(ActionEvent::new("public_mint")
+            .sender(info.sender)
+            .recipient(owner)
+            .price(&total)
+            .add_attribute("count", quantity.to_string())
+            .into_response()
+            .add_events(events)
+            .set_data(to_binary(&MintBatchResponse { token_ids })?))
+    }
+
+    /// Mints to the sender, who proves its allocation with a proof of the allowlist
+    pub fn allowlist_mint(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        proof: Vec<HexBinary>,
+        allocation: u32,
+        quantity: Option<u32>,
+    ) -> Result<Response, ContractError> {
+        let root = self
+            .merkle_root
+            .may_load(deps.storage)?
+            .ok_or(ContractError::AllowlistNotActive {})?;
+        check_allowlisted(&root, &info.sender, &proof, allocation)?;
+        // the allowlist mint is free, paid allowlist mints go through phases
+        check_payment(&info, &Coin::default())?;
+
+        let quantity = quantity.unwrap_or(1);
+        if quantity == 0 || quantity > MAX_BATCH_SIZE as u32 {
+            return Err(ContractError::InvalidMintQuantity {
+                max: MAX_BATCH_SIZE as u32,
+            });
+        }
+        let minted = self
+            .allowlist_minted
+            .may_load(deps.storage, (&root, &info.sender))?
+            .unwrap_or_default();
+        let remaining = allocation.saturating_sub(minted);
+        if quantity > remaining {
+            return Err(ContractError::AllocationExceeded { remaining });
+        }
+        self.check_supply(deps.as_ref(), quantity.into())?;
+
+        self.allowlist_minted
+            .save(deps.storage, (&root, &info.sender), &(minted + quantity))?;
+        let (token_ids, events) = self.mint_sequential(
+            deps.storage,
+            &env.block,
+            &info.sender,
+            &info.sender,
+            quantity,
+        )?;
+
+        Ok(ActionEvent::new("allowlist_mint")
+            .sender(info.sender)
+            .add_attribute("count", quantity.to_string())
+            .add_attribute("allocation", allocation.to_string())
+            .into_response()
+            .add_events(events)
+            .set_data(to_binary(&MintBatchResponse { token_ids })?))
+    }
+
+    /// Mints to the sender on the terms of the phase running at the block time
+    pub fn phase_mint(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        quantity: Option<u32>,
+        proof: Option<Vec<HexBinary>>,
+        allocation: Option<u32>,
+    ) -> Result<Response, ContractError> {
+        let (index, phase) = self
+            .current_phase(deps.storage, env.block.time)?
+            .ok_or(ContractError::NoActivePhase {})?;
+
+        // the allocation counts across every use of the root, the wallet limit per phase
+        let allowlisted = match &phase.eligibility {
+            Eligibility::Public => None,
+            Eligibility::Allowlist { root } => {
+                let (proof, allocation) = proof
+                    .zip(allocation)
+                    .ok_or(ContractError::InvalidMerkleProof {})?;
+                check_allowlisted(root, &info.sender, &proof, allocation)?;
+                let root_minted = self
+                    .allowlist_minted
+                    .may_load(deps.storage, (root, &info.sender))?
+                    .unwrap_or_default();
+
+                Some((root, allocation, root_minted))
+            }
+        };
+
+        let quantity = quantity.unwrap_or(1);
+        if quantity == 0 || quantity > MAX_BATCH_SIZE as u32 {
+            return Err(ContractError::InvalidMintQuantity {
+                max: MAX_BATCH_SIZE as u32,
+            });
+        }
+        let minted = self
+            .phase_minted
+            .may_load(deps.storage, (index, &info.sender))?
+            .unwrap_or_default();
+        let mut remaining = phase.per_wallet_limit.saturating_sub(minted);
+        if let Some((_, allocation, root_minted)) = allowlisted {
+            remaining = remaining.min(allocation.saturating_sub(root_minted));
+        }
+        if quantity > remaining {
+            return Err(ContractError::AllocationExceeded { remaining });
+        }
+        let total = Coin {
+            denom: phase.price.denom,
+            amount: phase
+                .price
+                .amount
+                .checked_mul(quantity.into())
+                .map_err(StdError::from)?,
+        };
+        check_payment(&info, &total)?;
+        self.check_supply(deps.as_ref(), quantity.into())?;
+
+        self.phase_minted
+            .save(deps.storage, (index, &info.sender), &(minted + quantity))?;
+        if let Some((root, _, root_minted)) = allowlisted {
+            self.allowlist_minted.save(
+                deps.storage,
+                (root, &info.sender),
+                &(root_minted + quantity),
+            )?;
+        }
+        let (token_ids, events) = self.mint_sequential(
+            deps.storage,
+            &env.block,
+            &info.sender,
+            &info.sender,
+            quantity,
+        )?;
+
+        Ok(ActionEvent::new("phase_mint")
+            .sender(info.sender)
+            .price(&total)
+            .add_attribute("phase", phase.name)
+            .add_attribute("count", quantity.to_string())
+            .into_response()
+            .add_events(events)
+            .set_data(to_binary(&MintBatchResponse { token_ids })?))
+    }
+
+    /// Mints tokens with the next ids, without metadata and with the collection royalty
+    fn mint_sequential(
+        &self,
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        minter: &Addr,
+        owner: &Addr,
+        quantity: u32,
+    ) -> Result<(Vec<String>, Vec<Event>), ContractError> {
+        let mut token_ids = Vec::with_capacity(quantity as usize);
+        let mut events = Vec::with_capacity(quantity as usize);
+        for _ in 0..quantity {
+            let token = Token {
+                owner: owner.clone(),
+                token_id: self.resolve_token_id(storage, None)?,
+                token_uri: None,
+                approvals: vec![],
+                royalty: None,
+                extension: T::default(),
+            };
+            self.save_new_token(storage, block, &token, false)?;
+
+            events.push(Event::from(mint_event(minter, &token)));
+            token_ids.push(token.token_id);
+        }
+
+        Ok((token_ids, events))
+    }
+}
+
+impl<'a, T> Contract<'a, T>
+where
+    T: Serialize + DeserializeOwned + Clone,
+{
+    pub fn mint(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: TokenMsg<T>,
+    ) -> Result<Response, ContractError> {
+        self.check_has_role(deps.as_ref(), Role::Minter, &info)?;
+        self.check_supply(deps.as_ref(), 1)?;
+
+        let token_id = self.resolve_token_id(deps.storage, msg.token_id.clone())?;
+        let soulbound = msg.soulbound;
+        let token = self.create_token(deps.as_ref(), token_id, msg)?;
+        self.save_new_token(deps.storage, &env.block, &token, soulbound)?;
+
+        Ok(mint_event(&info.sender, &token)
+            .into_response()
+            .set_data(to_binary(&MintResponse {
+                token_id: token.token_id,
+            })?))
+    }
+
+    pub fn mint_batch(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msgs: Vec<TokenMsg<T>>,
+    ) -> Result<Response, ContractError> {
+        self.check_has_role(deps.as_ref(), Role::Minter, &info)?;
+
+        if msgs.is_empty() || msgs.len() > MAX_BATCH_SIZE {
+            return Err(ContractError::InvalidBatchSize {
+                max: MAX_BATCH_SIZE as u32,
+            });
+        }
+        self.check_supply(deps.as_ref(), msgs.len() as u64)?;
+
+        // every token is validated before any of them is stored, so a batch is minted whole or not at all
+        let mut unique_ids = BTreeSet::new();
+        let mut tokens = Vec::with_capacity(msgs.len());
+        for msg in msgs {
+            let token_id = self.resolve_token_id(deps.storage, msg.token_id.clone())?;
+            if !unique_ids.insert(token_id.clone()) {
+                return Err(ContractError::DuplicateTokenId { token_id });
+            }
+            if self.tokens.has(deps.storage, &token_id) {
+                return Err(ContractError::TokenAlreadyExistsError {});
+            }
+
+            let soulbound = msg.soulbound;
+            tokens.push((self.create_token(deps.as_ref(), token_id, msg)?, soulbound));
+        }
+
+        let token_ids: Vec<String> = tokens
+            .iter()
+            .map(|(token, _)| token.token_id.clone())
+            .collect();
+        let mut events = Vec::with_capacity(tokens.len());
+        for (token, soulbound) in tokens {
+            self.save_new_token(deps.storage, &env.block, &token, soulbound)?;
+
+            events.push(Event::from(mint_event(&info.sender, &token)));
+        }
 
-        Ok(Response::new()
-            .add_attribute("action", "mint")
-            .add_attribute("token_id", msg.token_id)
-            .add_attribute("owner", msg.owner)
-            .add_attribute("token_uri", msg.token_uri.unwrap_or("zxc".to_string())))
+        Ok(ActionEvent::new("mint_batch")
+            .sender(info.sender)
+            .add_attribute("count", events.len().to_string())
+            .into_response()
+            .add_events(events)
+            .set_data(to_binary(&MintBatchResponse { token_ids })?))
     }
 
     pub fn send_nft(
         &self,
         deps: DepsMut,
+        env: Env,
         info: MessageInfo,
         token_id: String,
         contract: String,
         msg: Binary,
     ) -> Result<Response, ContractError> {
-        self.internal_transfer(deps, info.clone(), &token_id, &contract)?;
+        self.internal_transfer(
+            deps,
+            &env,
+            info.clone(),
+            &token_id,
+            &contract,
+            ProvenanceKind::Send,
+        )?;
 
         let send_msg = Cw721ReceiveMsg {
-            sender: info.sender.into_string(),
+            sender: info.sender.to_string(),
             token_id: token_id.clone(),
             msg,
         };
 
-        Ok(Response::new()
-            .add_message(send_msg.into_cosmos_msg(contract.clone())?)
-            .add_attribute("action", "transfer")
-            .add_attribute("receiver", contract)
-            .add_attribute("token_id", token_id))
-    }
-
-    pub fn receive_nft(&self, msg: Cw721ReceiveMsg) -> Result<Response, ContractError> {
-        let inner_msg: String = from_binary(&msg.msg)?;
-
-        Ok(Response::new()
-            .add_attribute("action", "receive")
-            .add_attribute("sender", msg.sender)
-            .add_attribute("token_id", msg.token_id)
-            .add_attribute("msg", inner_msg))
+        Ok(ActionEvent::new("send_nft")
+            .sender(info.sender)
+            .recipient(contract.clone())
+            .token_id(token_id)
+            .into_response()
+            .add_message(send_msg.into_cosmos_msg(contract)?))
     }
 
     pub fn transfer_nft(
         &self,
         deps: DepsMut,
+        env: Env,
         info: MessageInfo,
         token_id: String,
         to: String,
     ) -> Result<Response, ContractError> {
-        self.internal_transfer(deps, info, &token_id, &to)?;
+        self.internal_transfer(
+            deps,
+            &env,
+            info.clone(),
+            &token_id,
+            &to,
+            ProvenanceKind::Transfer,
+        )?;
 
-        Ok(Response::new()
-            .add_attribute("action", "transfer")
-            .add_attribute("receiver", to)
-            .add_attribute("token_id", token_id))
+        Ok(ActionEvent::new("transfer_nft")
+            .sender(info.sender)
+            .recipient(to)
+            .token_id(token_id)
+            .into_response())
     }
 
-    pub fn internal_transfer(
+    pub fn batch_transfer_nft(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_ids: Vec<String>,
+        to: String,
+    ) -> Result<Response, ContractError> {
+        self.check_can_send_batch(deps.as_ref(), &env, &info, &token_ids)?;
+
+        let mut events = Vec::with_capacity(token_ids.len());
+        for token_id in token_ids {
+            self.internal_transfer(
+                deps.branch(),
+                &env,
+                info.clone(),
+                &token_id,
+                &to,
+                ProvenanceKind::Transfer,
+            )?;
+
+            events.push(Event::from(
+                ActionEvent::new("transfer_nft")
+                    .sender(info.sender.clone())
+                    .recipient(to.clone())
+                    .token_id(token_id),
+            ));
+        }
+
+        Ok(ActionEvent::new("batch_transfer_nft")
+            .sender(info.sender)
+            .recipient(to)
+            .add_attribute("count", events.len().to_string())
+            .into_response()
+            .add_events(events))
+    }
+
+    pub fn batch_send_nft(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_ids: Vec<String>,
+        contract: String,
+        msg: Binary,
+    ) -> Result<Response, ContractError> {
+        self.check_can_send_batch(deps.as_ref(), &env, &info, &token_ids)?;
+
+        for token_id in &token_ids {
+            self.internal_transfer(
+                deps.branch(),
+                &env,
+                info.clone(),
+                token_id,
+                &contract,
+                ProvenanceKind::Send,
+            )?;
+        }
+
+        let receiver = deps.api.addr_validate(&contract)?;
+        let messages = if self.batch_receivers.has(deps.storage, &receiver) {
+            vec![Cw721BatchReceiveMsg {
+                sender: info.sender.to_string(),
+                token_ids: token_ids.clone(),
+                msg,
+            }
+            .into_cosmos_msg(contract.clone())?]
+        } else {
+            token_ids
+                .iter()
+                .map(|token_id| {
+                    Cw721ReceiveMsg {
+                        sender: info.sender.to_string(),
+                        token_id: token_id.clone(),
+                        msg: msg.clone(),
+                    }
+                    .into_cosmos_msg(contract.clone())
+                })
+                .collect::<StdResult<Vec<_>>>()?
+        };
+
+        let events: Vec<Event> = token_ids
+            .into_iter()
+            .map(|token_id| {
+                ActionEvent::new("send_nft")
+                    .sender(info.sender.clone())
+                    .recipient(contract.clone())
+                    .token_id(token_id)
+                    .into()
+            })
+            .collect();
+
+        Ok(ActionEvent::new("batch_send_nft")
+            .sender(info.sender)
+            .recipient(contract)
+            .add_attribute("count", events.len().to_string())
+            .into_response()
+            .add_events(events)
+            .add_messages(messages))
+    }
+
+    /// Lets a receiver contract get a single `BatchReceiveNft` callback for `BatchSendNft`
+    pub fn set_batch_receive(
         &self,
         deps: DepsMut,
         info: MessageInfo,
-        token_id: &str,
-        to: &str,
-    ) -> Result<Token, ContractError> {
-        let mut token = self.tokens.load(deps.storage, token_id)?;
+        enabled: bool,
+    ) -> Result<Response, ContractError> {
+        if enabled {
+            self.batch_receivers.save(deps.storage, &info.sender, &())?;
+        } else {
+            self.batch_receivers.remove(deps.storage, &info.sender);
+        }
 
-        if token.owner != info.sender {
-            return Err(ContractError::Unauthorized {});
+        Ok(ActionEvent::new("set_batch_receive")
+            .sender(info.sender)
+            .add_attribute("enabled", enabled.to_string())
+            .into_response())
+    }
+
+    pub fn approve(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        spender: String,
+        token_id: String,
+        expires: Option<Expiration>,
+    ) -> Result<Response, ContractError> {
+        let mut token = self.tokens.load(deps.storage, &token_id)?;
+        self.check_can_approve(deps.as_ref(), &env, &info, &token)?;
+
+        let expires = expires.unwrap_or_default();
+        if expires.is_expired(&env.block) {
+            return Err(ContractError::Expired {});
         }
 
-        token.owner = deps.api.addr_validate(to)?;
-        self.tokens.save(deps.storage, token_id, &token)?;
+        let spender_addr = deps.api.addr_validate(&spender)?;
+        token
+            .approvals
+            .retain(|approval| approval.spender != spender_addr);
+        token.approvals.push(Approval {
+            spender: spender_addr,
+            expires,
+        });
+        self.tokens.save(deps.storage, &token_id, &token)?;
 
-        return Ok(token);
+        Ok(ActionEvent::new("approve")
+            .sender(info.sender)
+            .token_id(token_id)
+            .add_attribute("spender", spender)
+            .into_response())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use cosmwasm_std::to_binary;
+    pub fn revoke(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        spender: String,
+        token_id: String,
+    ) -> Result<Response, ContractError> {
+        let mut token = self.tokens.load(deps.storage, &token_id)?;
+        self.check_can_approve(deps.as_ref(), &env, &info, &token)?;
 
-    use crate::{
-        msg::ExecuteMsg,
-        utils::test_utils::{
-            get_mock_info, initialize_contract, mint_token, MINTER, OWNER, TOKEN_ID,
-        },
-        ContractError,
-    };
+        let spender_addr = deps.api.addr_validate(&spender)?;
+        token
+            .approvals
+            .retain(|approval| approval.spender != spender_addr);
+        self.tokens.save(deps.storage, &token_id, &token)?;
 
-    const STRANGER: &str = "stranger";
+        Ok(ActionEvent::new("revoke")
+            .sender(info.sender)
+            .token_id(token_id)
+            .add_attribute("spender", spender)
+            .into_response())
+    }
 
-    #[test]
-    fn should_fail_mint_when_called_not_by_minter() {
-        let (mut deps, contract, env, _) = initialize_contract();
+    pub fn approve_all(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        operator: String,
+        expires: Option<Expiration>,
+    ) -> Result<Response, ContractError> {
+        let expires = expires.unwrap_or_default();
+        if expires.is_expired(&env.block) {
+            return Err(ContractError::Expired {});
+        }
 
-        let mint_res = mint_token(&contract, &mut deps, env, STRANGER, TOKEN_ID).unwrap_err();
+        let operator_addr = deps.api.addr_validate(&operator)?;
+        self.operators
+            .save(deps.storage, (&info.sender, &operator_addr), &expires)?;
 
-        assert!(matches!(mint_res, ContractError::Unauthorized {}));
+        Ok(ActionEvent::new("approve_all")
+            .sender(info.sender)
+            .add_attribute("operator", operator)
+            .into_response())
     }
 
-    #[test]
-    fn should_mint_token() {
-        let (mut deps, contract, env, _) = initialize_contract();
+    pub fn revoke_all(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        operator: String,
+    ) -> Result<Response, ContractError> {
+        let operator_addr = deps.api.addr_validate(&operator)?;
+        self.operators
+            .remove(deps.storage, (&info.sender, &operator_addr));
 
-        let mint_res = mint_token(&contract, &mut deps, env, MINTER, TOKEN_ID).unwrap();
+        Ok(ActionEvent::new("revoke_all")
+            .sender(info.sender)
+            .add_attribute("operator", operator)
+            .into_response())
+    }
 
-        assert_eq!(mint_res.messages.len(), 0);
-        insta::assert_json_snapshot!(contract.tokens.load(&deps.storage, TOKEN_ID).unwrap());
+    pub fn burn(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+    ) -> Result<Response, ContractError> {
+        let token = self.tokens.load(deps.storage, &token_id)?;
+        // the minter can revoke soulbound tokens, which their owners cannot hand back
+        let revoked_by_minter = self.is_soulbound(deps.storage, &token_id)?
+            && self.has_role(deps.storage, Role::Minter, &info.sender);
+        if !revoked_by_minter {
+            self.check_can_send(deps.as_ref(), &env, &info, &token)?;
+        }
+
+        self.tokens.remove(deps.storage, &token_id)?;
+        self.frozen_tokens.remove(deps.storage, &token_id);
+        self.soulbound_tokens.remove(deps.storage, &token_id);
+        self.clear_provenance(deps.storage, &token_id)?;
+        self.decrement_tokens(deps.storage)?;
+
+        Ok(ActionEvent::new("burn")
+            .sender(info.sender)
+            .token_id(token_id)
+            .into_response())
     }
 
-    #[test]
-    fn should_fail_nft_transfer_when_called_not_by_owner() {
-        let (mut deps, contract, env, _) = initialize_contract();
-        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+    pub fn update_token_uri(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        token_id: String,
+        token_uri: Option<String>,
+    ) -> Result<Response, ContractError> {
+        self.check_has_role(deps.as_ref(), Role::MetadataEditor, &info)?;
+        self.check_metadata_mutable(deps.as_ref(), &token_id)?;
 
-        let transfer_result = contract
-            .execute(
-                deps.as_mut(),
-                env,
-                get_mock_info(STRANGER),
-                ExecuteMsg::TransferNft {
-                    token_id: TOKEN_ID.to_string(),
-                    to: STRANGER.to_string(),
-                },
-            )
-            .unwrap_err();
+        let mut token = self.tokens.load(deps.storage, &token_id)?;
+        token.token_uri = token_uri;
+        self.tokens.save(deps.storage, &token_id, &token)?;
 
-        assert!(matches!(transfer_result, ContractError::Unauthorized {}))
+        Ok(ActionEvent::new("update_token_uri")
+            .sender(info.sender)
+            .token_id(token_id)
+            .add_attribute("token_uri", token.token_uri.unwrap_or_default())
+            .into_response())
     }
 
-    #[test]
-    fn should_transfer_nft() {
-        let (mut deps, contract, env, _) = initialize_contract();
-        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+    /// Permanently locks the metadata of a single token
+    pub fn freeze_token(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        token_id: String,
+    ) -> Result<Response, ContractError> {
+        self.check_has_role(deps.as_ref(), Role::MetadataEditor, &info)?;
+        self.check_metadata_mutable(deps.as_ref(), &token_id)?;
 
-        let transfer_result = contract
-            .execute(
-                deps.as_mut(),
-                env,
-                get_mock_info(OWNER),
-                ExecuteMsg::TransferNft {
-                    token_id: TOKEN_ID.to_string(),
-                    to: STRANGER.to_string(),
-                },
-            )
-            .unwrap();
+        self.tokens.load(deps.storage, &token_id)?;
+        self.frozen_tokens.save(deps.storage, &token_id, &())?;
 
-        assert_eq!(transfer_result.messages.len(), 0);
-        insta::assert_json_snapshot!(contract.tokens.load(&deps.storage, TOKEN_ID).unwrap());
+        Ok(ActionEvent::new("freeze_token")
+            .sender(info.sender)
+            .token_id(token_id)
+            .into_response())
     }
 
-    #[test]
-    fn should_fail_nft_send_when_called_not_by_owner() {
-        let (mut deps, contract, env, _) = initialize_contract();
-        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+    /// Permanently locks the metadata of every token, including the ones minted later
+    pub fn freeze_metadata(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        self.check_has_role(deps.as_ref(), Role::MetadataEditor, &info)?;
+        if self.metadata_frozen(deps.storage)? {
+            return Err(ContractError::MetadataFrozen {});
+        }
 
-        let send_result = contract
-            .execute(
-                deps.as_mut(),
-                env,
-                get_mock_info(STRANGER),
-                ExecuteMsg::SendNft {
-                    token_id: TOKEN_ID.to_string(),
-                    contract: STRANGER.to_string(),
-                    msg: to_binary("Hello, it's fail!").unwrap(),
-                },
-            )
-            .unwrap_err();
+        self.metadata_frozen.save(deps.storage, &true)?;
 
-        assert!(matches!(send_result, ContractError::Unauthorized {}))
+        Ok(ActionEvent::new("freeze_metadata")
+            .sender(info.sender)
+            .into_response())
     }
 
-    #[test]
-    fn should_send_nft() {
-        let (mut deps, contract, env, _) = initialize_contract();
-        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+    /// Takes the next id from the counter when the collection assigns ids itself
+    fn resolve_token_id(
+        &self,
+        storage: &mut dyn Storage,
+        token_id: Option<String>,
+    ) -> Result<String, ContractError> {
+        let auto_token_id = self.contract_info.load(storage)?.auto_token_id;
 
-        let send_result = contract
-            .execute(
-                deps.as_mut(),
-                env,
-                get_mock_info(OWNER),
-                ExecuteMsg::SendNft {
-                    token_id: TOKEN_ID.to_string(),
-                    contract: STRANGER.to_string(),
-                    msg: to_binary("Hello, it's success!").unwrap(),
-                },
-            )
-            .unwrap();
+        match (auto_token_id, token_id) {
+            (false, Some(token_id)) => {
+                self.check_token_id(storage, &token_id)?;
+
+                Ok(token_id)
+            }
+            (false, None) => Err(ContractError::MissingTokenId {}),
+            (true, Some(_)) => Err(ContractError::UnexpectedTokenId {}),
+            (true, None) => {
+                let token_id = self.next_token_id.may_load(storage)?.unwrap_or(1);
+                self.next_token_id.save(storage, &(token_id + 1))?;
+
+                Ok(token_id.to_string())
+            }
+        }
+    }
+
+    fn create_token(
+        &self,
+        deps: Deps,
+        token_id: String,
+        msg: TokenMsg<T>,
+    ) -> Result<Token<T>, ContractError> {
+        let royalty = msg
+            .royalty
+            .map(|royalty| self.validate_royalty(deps, royalty))
+            .transpose()?;
+
+        Ok(Token {
+            owner: deps.api.addr_validate(&msg.owner)?,
+            token_id,
+            token_uri: msg.token_uri,
+            approvals: vec![],
+            royalty,
+            extension: msg.extension,
+        })
+    }
+
+    fn save_new_token(
+        &self,
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        token: &Token<T>,
+        soulbound: bool,
+    ) -> Result<(), ContractError> {
+        self.tokens
+            .update(storage, &token.token_id, |old| match old {
+                Some(_) => Err(ContractError::TokenAlreadyExistsError {}),
+                None => Ok(token.clone()),
+            })?;
+        if soulbound {
+            self.soulbound_tokens.save(storage, &token.token_id, &())?;
+        }
+        self.record_provenance(
+            storage,
+            block,
+            &token.token_id,
+            ProvenanceKind::Mint,
+            None,
+            token.owner.clone(),
+        )?;
+        self.increment_tokens(storage)?;
+
+        Ok(())
+    }
+
+    pub fn internal_transfer(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        info: MessageInfo,
+        token_id: &str,
+        to: &str,
+        kind: ProvenanceKind,
+    ) -> Result<Token<T>, ContractError> {
+        let mut token = self.tokens.load(deps.storage, token_id)?;
+        self.check_can_send(deps.as_ref(), env, &info, &token)?;
+        if self.is_soulbound(deps.storage, token_id)? {
+            return Err(ContractError::NonTransferable {});
+        }
+
+        let from = token.owner;
+        token.owner = deps.api.addr_validate(to)?;
+        token.approvals = vec![];
+        self.tokens.save(deps.storage, token_id, &token)?;
+        self.record_provenance(
+            deps.storage,
+            &env.block,
+            token_id,
+            kind,
+            Some(from),
+            token.owner.clone(),
+        )?;
+
+        Ok(token)
+    }
+}
+
+impl<'a, T> Contract<'a, T>
+where
+    T: Serialize + DeserializeOwned + Clone,
+{
+    pub fn update_ownership(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        action: OwnershipAction,
+    ) -> Result<Response, ContractError> {
+        match action {
+            OwnershipAction::TransferOwnership { new_owner, expiry } => {
+                self.transfer_ownership(deps, env, info, new_owner, expiry)
+            }
+            OwnershipAction::AcceptOwnership => self.accept_ownership(deps, env, info),
+            OwnershipAction::RenounceOwnership => self.renounce_ownership(deps, info),
+        }
+    }
+
+    pub fn transfer_ownership(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        new_owner: String,
+        expiry: Option<Expiration>,
+    ) -> Result<Response, ContractError> {
+        self.check_is_owner(deps.as_ref(), &info)?;
+
+        let expiry = expiry.unwrap_or_default();
+        if expiry.is_expired(&env.block) {
+            return Err(ContractError::Expired {});
+        }
+
+        let pending_owner = PendingOwner {
+            new_owner: deps.api.addr_validate(&new_owner)?,
+            expiry,
+        };
+        self.pending_owner.save(deps.storage, &pending_owner)?;
+
+        Ok(ActionEvent::new("transfer_ownership")
+            .sender(info.sender)
+            .recipient(new_owner)
+            .add_attribute("expiry", expiry.to_string())
+            .into_response())
+    }
+
+    pub fn accept_ownership(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        let pending_owner = self
+            .pending_owner
+            .may_load(deps.storage)?
+            .ok_or(ContractError::NoPendingOwner {})?;
+
+        if pending_owner.new_owner != info.sender {
+            return Err(ContractError::NotPendingOwner {});
+        }
+
+        if pending_owner.expiry.is_expired(&env.block) {
+            return Err(ContractError::TransferExpired {});
+        }
+
+        // the owner roles move along with the ownership
+        let previous_owner = self.owner.may_load(deps.storage)?;
+        for role in Role::OWNER_ROLES {
+            if let Some(previous_owner) = &previous_owner {
+                self.remove_role(deps.storage, role, previous_owner);
+            }
+            self.save_role(deps.storage, role, &pending_owner.new_owner)?;
+        }
+        self.owner.save(deps.storage, &pending_owner.new_owner)?;
+        self.pending_owner.remove(deps.storage);
+
+        Ok(ActionEvent::new("accept_ownership")
+            .sender(info.sender)
+            .into_response())
+    }
+
+    pub fn renounce_ownership(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        self.check_is_owner(deps.as_ref(), &info)?;
+
+        for role in Role::OWNER_ROLES {
+            self.remove_role(deps.storage, role, &info.sender);
+        }
+        self.owner.remove(deps.storage);
+        self.pending_owner.remove(deps.storage);
+
+        Ok(ActionEvent::new("renounce_ownership")
+            .sender(info.sender)
+            .into_response())
+    }
+
+    pub fn update_minter(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        minter: String,
+    ) -> Result<Response, ContractError> {
+        self.check_is_owner(deps.as_ref(), &info)?;
+
+        let minter_addr = deps.api.addr_validate(&minter)?;
+        let previous_minter = self.minter.load(deps.storage)?;
+        for role in Role::MINTER_ROLES {
+            self.remove_role(deps.storage, role, &previous_minter);
+            self.save_role(deps.storage, role, &minter_addr)?;
+        }
+        self.minter.save(deps.storage, &minter_addr)?;
+
+        Ok(ActionEvent::new("update_minter")
+            .sender(info.sender)
+            .recipient(minter)
+            .into_response())
+    }
+
+    pub fn grant_role(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        role: Role,
+        address: String,
+    ) -> Result<Response, ContractError> {
+        self.check_has_role(deps.as_ref(), Role::Admin, &info)?;
+
+        let addr = deps.api.addr_validate(&address)?;
+        self.save_role(deps.storage, role, &addr)?;
+
+        Ok(ActionEvent::new("grant_role")
+            .sender(info.sender)
+            .recipient(address)
+            .add_attribute("role", role.as_str())
+            .into_response())
+    }
+
+    /// Admins can revoke each other, but the last admin cannot be removed
+    pub fn revoke_role(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        role: Role,
+        address: String,
+    ) -> Result<Response, ContractError> {
+        self.check_has_role(deps.as_ref(), Role::Admin, &info)?;
+
+        let addr = deps.api.addr_validate(&address)?;
+        if role == Role::Admin && self.owner.may_load(deps.storage)? == Some(addr.clone()) {
+            return Err(ContractError::CannotRevokeOwnerAdmin {});
+        }
+        if role == Role::Admin && self.has_role(deps.storage, role, &addr) {
+            let admins = self
+                .roles
+                .prefix(role.as_str())
+                .keys(deps.storage, None, None, Order::Ascending)
+                .take(2)
+                .count();
+            if admins < 2 {
+                return Err(ContractError::CannotRevokeLastAdmin {});
+            }
+        }
+        self.remove_role(deps.storage, role, &addr);
+
+        Ok(ActionEvent::new("revoke_role")
+            .sender(info.sender)
+            .add_attribute("role", role.as_str())
+            .add_attribute("address", address)
+            .into_response())
+    }
+
+    pub fn update_sale(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        sale: Option<SaleConfig>,
+    ) -> Result<Response, ContractError> {
+        self.check_is_owner(deps.as_ref(), &info)?;
+
+        let event = ActionEvent::new("update_sale").sender(info.sender);
+        let event = match sale {
+            Some(sale) => {
+                check_sale_config(&self.contract_info.load(deps.storage)?, &sale)?;
+                self.sale.save(deps.storage, &sale)?;
+
+                event
+                    .price(&sale.price)
+                    .add_attribute("per_tx_limit", sale.per_tx_limit.to_string())
+                    .add_attribute("start_time", sale.start_time.to_string())
+            }
+            None => {
+                self.sale.remove(deps.storage);
+
+                event
+            }
+        };
+
+        Ok(event.into_response())
+    }
+
+    pub fn set_merkle_root(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        root: Option<HexBinary>,
+    ) -> Result<Response, ContractError> {
+        self.check_is_owner(deps.as_ref(), &info)?;
+
+        let event = ActionEvent::new("set_merkle_root").sender(info.sender);
+        let event = match root {
+            Some(root) => {
+                if root.len() != 32 {
+                    return Err(ContractError::InvalidMerkleRoot {});
+                }
+                // allowlist mints take sequential ids, like the public mint
+                if !self.contract_info.load(deps.storage)?.auto_token_id {
+                    return Err(ContractError::AutoTokenIdRequired {});
+                }
+                self.merkle_root.save(deps.storage, &root)?;
+
+                event.add_attribute("root", root.to_hex())
+            }
+            None => {
+                self.merkle_root.remove(deps.storage);
+
+                event
+            }
+        };
+
+        Ok(event.into_response())
+    }
+
+    pub fn update_phases(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        phases: Vec<MintPhase>,
+    ) -> Result<Response, ContractError> {
+        self.check_is_owner(deps.as_ref(), &info)?;
+
+        let current = self.phases.may_load(deps.storage)?.unwrap_or_default();
+        if current
+            .iter()
+            .any(|phase| phase.start_time <= env.block.time)
+        {
+            return Err(ContractError::PhasesLocked {});
+        }
+        check_phases(
+            &self.contract_info.load(deps.storage)?,
+            &phases,
+            env.block.time,
+        )?;
+        self.phases.save(deps.storage, &phases)?;
+
+        Ok(ActionEvent::new("update_phases")
+            .sender(info.sender)
+            .add_attribute("count", phases.len().to_string())
+            .into_response())
+    }
+
+    pub fn withdraw(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        recipient: String,
+        amount: Coin,
+    ) -> Result<Response, ContractError> {
+        self.check_is_owner(deps.as_ref(), &info)?;
+
+        deps.api.addr_validate(&recipient)?;
+        let balance = deps
+            .querier
+            .query_balance(&env.contract.address, &amount.denom)?;
+        if amount.amount.is_zero() || amount.amount > balance.amount {
+            return Err(ContractError::InsufficientBalance {});
+        }
+
+        Ok(ActionEvent::new("withdraw")
+            .sender(info.sender)
+            .recipient(recipient.clone())
+            .add_attribute("amount", amount.to_string())
+            .into_response()
+            .add_message(BankMsg::Send {
+                to_address: recipient,
+                amount: vec![amount],
+            }))
+    }
+
+    /// Halts the operations of `scope`, everything if no scope is given
+    pub fn pause(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        scope: Option<PauseScope>,
+    ) -> Result<Response, ContractError> {
+        self.check_has_role(deps.as_ref(), Role::Pauser, &info)?;
+
+        let scope = scope.unwrap_or(PauseScope::All);
+        self.paused.save(deps.storage, &scope)?;
+
+        Ok(ActionEvent::new("pause")
+            .sender(info.sender)
+            .add_attribute("scope", scope.as_str())
+            .into_response())
+    }
+
+    pub fn unpause(&self, deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+        self.check_has_role(deps.as_ref(), Role::Pauser, &info)?;
+
+        self.paused.remove(deps.storage);
+
+        Ok(ActionEvent::new("unpause")
+            .sender(info.sender)
+            .into_response())
+    }
+
+    /// The cap can only be lowered, and never below the current supply
+    pub fn update_max_supply(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        max_supply: u64,
+    ) -> Result<Response, ContractError> {
+        self.check_is_owner(deps.as_ref(), &info)?;
+
+        let mut contract_info = self.contract_info.load(deps.storage)?;
+        if contract_info
+            .max_supply
+            .is_some_and(|current| max_supply > current)
+        {
+            return Err(ContractError::CannotRaiseMaxSupply {});
+        }
+        if max_supply == 0 || max_supply < self.token_count(deps.storage)? {
+            return Err(ContractError::InvalidMaxSupply {});
+        }
+
+        contract_info.max_supply = Some(max_supply);
+        self.contract_info.save(deps.storage, &contract_info)?;
+
+        Ok(ActionEvent::new("update_max_supply")
+            .sender(info.sender)
+            .add_attribute("max_supply", max_supply.to_string())
+            .into_response())
+    }
+}
+
+impl<'a, T> Contract<'a, T>
+where
+    T: Serialize + DeserializeOwned + Clone,
+{
+    /// The owner of the token or one of the owner's operators may grant or revoke approvals on it.
+    pub fn check_can_approve(
+        &self,
+        deps: Deps,
+        env: &Env,
+        info: &MessageInfo,
+        token: &Token<T>,
+    ) -> Result<(), ContractError> {
+        if token.owner == info.sender || self.is_operator(deps, env, &token.owner, &info.sender)? {
+            return Ok(());
+        }
+
+        Err(ContractError::Unauthorized {})
+    }
+
+    /// The owner of the token, one of the owner's operators or any spender
+    /// holding a non-expired approval may move it.
+    pub fn check_can_send(
+        &self,
+        deps: Deps,
+        env: &Env,
+        info: &MessageInfo,
+        token: &Token<T>,
+    ) -> Result<(), ContractError> {
+        if token.owner == info.sender || self.is_operator(deps, env, &token.owner, &info.sender)? {
+            return Ok(());
+        }
+
+        let is_approved = token
+            .approvals
+            .iter()
+            .any(|approval| approval.spender == info.sender && !approval.is_expired(&env.block));
+
+        if !is_approved {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        Ok(())
+    }
+
+    /// Checks every token up front, so that a batch is transferred whole or not at all
+    pub fn check_can_send_batch(
+        &self,
+        deps: Deps,
+        env: &Env,
+        info: &MessageInfo,
+        token_ids: &[String],
+    ) -> Result<(), ContractError> {
+        if token_ids.is_empty() || token_ids.len() > MAX_BATCH_SIZE {
+            return Err(ContractError::InvalidBatchSize {
+                max: MAX_BATCH_SIZE as u32,
+            });
+        }
+
+        let mut unique_ids = BTreeSet::new();
+        for token_id in token_ids {
+            if !unique_ids.insert(token_id) {
+                return Err(ContractError::DuplicateTokenId {
+                    token_id: token_id.clone(),
+                });
+            }
+
+            let token = self.tokens.load(deps.storage, token_id)?;
+            self.check_can_send(deps, env, info, &token)?;
+            if self.is_soulbound(deps.storage, token_id)? {
+                return Err(ContractError::NonTransferable {});
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Ownership and pause management stay available, so that the owner can always lift a pause
+    pub fn check_not_paused(&self, deps: Deps, msg: &ExecuteMsg<T>) -> Result<(), ContractError> {
+        let scope = match self.paused.may_load(deps.storage)? {
+            Some(scope) => scope,
+            None => return Ok(()),
+        };
+
+        // the owner keeps administering the contract, and can move its funds, while it is paused
+        let blocked = match msg {
+            ExecuteMsg::Pause { .. }
+            | ExecuteMsg::Unpause {}
+            | ExecuteMsg::UpdateOwnership(_)
+            | ExecuteMsg::UpdateMinter { .. }
+            | ExecuteMsg::GrantRole { .. }
+            | ExecuteMsg::RevokeRole { .. }
+            | ExecuteMsg::Withdraw { .. } => false,
+            ExecuteMsg::Mint { .. }
+            | ExecuteMsg::MintBatch { .. }
+            | ExecuteMsg::PublicMint { .. }
+            | ExecuteMsg::AllowlistMint { .. }
+            | ExecuteMsg::PhaseMint { .. } => scope != PauseScope::Transfers,
+            ExecuteMsg::TransferNft { .. }
+            | ExecuteMsg::SendNft { .. }
+            | ExecuteMsg::BatchTransferNft { .. }
+            | ExecuteMsg::BatchSendNft { .. } => scope != PauseScope::Minting,
+            _ => scope == PauseScope::All,
+        };
+        if blocked {
+            return Err(ContractError::Paused {});
+        }
+
+        Ok(())
+    }
+
+    pub fn check_supply(&self, deps: Deps, amount: u64) -> Result<(), ContractError> {
+        if let Some(max_supply) = self.contract_info.load(deps.storage)?.max_supply {
+            if self.token_count(deps.storage)? + amount > max_supply {
+                return Err(ContractError::MaxSupplyReached {});
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn check_token_id(
+        &self,
+        storage: &dyn Storage,
+        token_id: &str,
+    ) -> Result<(), ContractError> {
+        let rules = self.token_id_rules.may_load(storage)?.unwrap_or_default();
+        let invalid = |reason: String| Err(ContractError::InvalidTokenId { reason });
+
+        if token_id.is_empty() {
+            return invalid("empty".to_string());
+        }
+        if token_id.chars().count() > rules.max_length as usize {
+            return invalid(format!("longer than {} characters", rules.max_length));
+        }
+        if let Some(c) = token_id.chars().find(|c| !rules.charset.contains(*c)) {
+            return invalid(format!("character {c:?} is not allowed"));
+        }
+        if rules.numeric_only {
+            if !token_id.chars().all(|c| c.is_ascii_digit()) {
+                return invalid("not a number".to_string());
+            }
+            if token_id.len() > 1 && token_id.starts_with('0') {
+                return invalid("leading zero".to_string());
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn check_metadata_mutable(&self, deps: Deps, token_id: &str) -> Result<(), ContractError> {
+        if self.metadata_frozen(deps.storage)? || self.frozen_tokens.has(deps.storage, token_id) {
+            return Err(ContractError::MetadataFrozen {});
+        }
+
+        Ok(())
+    }
+
+    pub fn check_has_role(
+        &self,
+        deps: Deps,
+        role: Role,
+        info: &MessageInfo,
+    ) -> Result<(), ContractError> {
+        if !self.has_role(deps.storage, role, &info.sender) {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        Ok(())
+    }
+
+    pub fn check_is_owner(&self, deps: Deps, info: &MessageInfo) -> Result<(), ContractError> {
+        match self.owner.may_load(deps.storage)? {
+            Some(owner) if owner == info.sender => Ok(()),
+            _ => Err(ContractError::Unauthorized {}),
+        }
+    }
+
+    pub fn validate_royalty(&self, deps: Deps, msg: RoyaltyMsg) -> Result<Royalty, ContractError> {
+        if msg.share > Decimal::one() {
+            return Err(ContractError::InvalidRoyaltyShare {});
+        }
+
+        Ok(Royalty {
+            payment_address: deps.api.addr_validate(&msg.payment_address)?,
+            share: msg.share,
+        })
+    }
+
+    pub fn is_operator(
+        &self,
+        deps: Deps,
+        env: &Env,
+        owner: &Addr,
+        operator: &Addr,
+    ) -> StdResult<bool> {
+        let expires = self.operators.may_load(deps.storage, (owner, operator))?;
+
+        Ok(expires.is_some_and(|expires| !expires.is_expired(&env.block)))
+    }
+}
+
+/// Collections accept tokens of any collection and echo the text message sent with them
+impl<'a, T> Cw721Receiver for Contract<'a, T>
+where
+    T: Serialize + DeserializeOwned + Clone,
+{
+    type Msg = String;
+    type Error = ContractError;
+
+    fn is_allowed_collection(&self, _deps: Deps, _collection: &Addr) -> StdResult<bool> {
+        Ok(true)
+    }
+
+    fn on_receive(
+        &self,
+        _deps: DepsMut,
+        _env: Env,
+        nft: ReceivedNft<String>,
+    ) -> Result<Response, ContractError> {
+        Ok(ActionEvent::new("receive_nft")
+            .sender(nft.sender)
+            .collection(nft.collection)
+            .token_id(nft.token_id)
+            .add_attribute("msg", nft.msg)
+            .into_response())
+    }
+}
+
+/// Public mint needs sequential ids, and cannot mint more than a batch at once
+fn check_sale_config(
+    contract_info: &CollectionInfo,
+    sale: &SaleConfig,
+) -> Result<(), ContractError> {
+    if !contract_info.auto_token_id
+        || sale.per_tx_limit == 0
+        || sale.per_tx_limit > MAX_BATCH_SIZE as u32
+    {
+        return Err(ContractError::InvalidSaleConfig {});
+    }
+
+    Ok(())
+}
+
+/// Phases mint sequential ids, start in the future, follow each other and last for some time
+fn check_phases(
+    contract_info: &CollectionInfo,
+    phases: &[MintPhase],
+    now: Timestamp,
+) -> Result<(), ContractError> {
+    let ordered = phases
+        .iter()
+        .all(|phase| now < phase.start_time && phase.start_time < phase.end_time)
+        && phases
+            .windows(2)
+            .all(|pair| pair[0].end_time <= pair[1].start_time);
+    let valid_roots = phases.iter().all(|phase| match &phase.eligibility {
+        Eligibility::Public => true,
+        Eligibility::Allowlist { root } => root.len() == 32,
+    });
+    if !contract_info.auto_token_id || !ordered || !valid_roots {
+        return Err(ContractError::InvalidPhases {});
+    }
+
+    Ok(())
+}
+
+fn check_allowlisted(
+    root: &HexBinary,
+    sender: &Addr,
+    proof: &[HexBinary],
+    allocation: u32,
+) -> Result<(), ContractError> {
+    let proof: Vec<&[u8]> = proof.iter().map(HexBinary::as_slice).collect();
+    if !verify_proof(root, leaf_hash(sender.as_str(), allocation), &proof) {
+        return Err(ContractError::InvalidMerkleProof {});
+    }
+
+    Ok(())
+}
+
+/// The funds have to be exactly the price, nothing for a free mint
+fn check_payment(info: &MessageInfo, price: &Coin) -> Result<(), ContractError> {
+    let paid_exactly = match info.funds.as_slice() {
+        [] => price.amount.is_zero(),
+        [paid] => paid == price,
+        _ => false,
+    };
+    if !paid_exactly {
+        return Err(ContractError::InvalidPayment {
+            expected: price.clone(),
+        });
+    }
+
+    Ok(())
+}
+
+fn mint_event<T>(minter: &Addr, token: &Token<T>) -> ActionEvent {
+    let event = ActionEvent::new("mint")
+        .sender(minter.clone())
+        .recipient(token.owner.clone())
+        .token_id(token.token_id.clone());
+
+    match &token.token_uri {
+        Some(token_uri) => event.add_attribute("token_uri", token_uri),
+        None => event,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{
+        coin, coins, from_binary,
+        testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier},
+        to_binary, Addr, BankMsg, Coin, Decimal, Env, MemoryStorage, OwnedDeps, Response,
+        Timestamp,
+    };
+    use cw_utils::Expiration;
+
+    use super::MAX_BATCH_SIZE;
+    use crate::{
+        merkle::MerkleTree,
+        msg::{ExecuteMsg, InstantiateMsg, OwnershipAction, RoyaltyMsg, TokenMsg},
+        response::{MintBatchResponse, MintResponse},
+        state::{
+            Contract, Eligibility, MintPhase, PauseScope, Role, SaleConfig, TokenIdCharset,
+            TokenIdRules,
+        },
+        utils::test_utils::{
+            get_default_instantiate_msg, get_mock_info, initialize_contract,
+            initialize_contract_with_msg, mint_token, MINTER, OWNER, TOKEN_ID,
+        },
+        ContractError,
+    };
+
+    const STRANGER: &str = "stranger";
+    const SPENDER: &str = "spender";
+
+    #[test]
+    fn should_fail_mint_when_called_not_by_minter() {
+        let (mut deps, contract, env, _) = initialize_contract();
+
+        let mint_res = mint_token(&contract, &mut deps, env, STRANGER, TOKEN_ID).unwrap_err();
+
+        assert!(matches!(mint_res, ContractError::Unauthorized {}));
+    }
+
+    #[test]
+    fn should_mint_token() {
+        let (mut deps, contract, env, _) = initialize_contract();
+
+        let mint_res = mint_token(&contract, &mut deps, env, MINTER, TOKEN_ID).unwrap();
+
+        assert_eq!(mint_res.messages.len(), 0);
+        insta::assert_json_snapshot!(contract.tokens.load(&deps.storage, TOKEN_ID).unwrap());
+        insta::assert_json_snapshot!(mint_res);
+    }
+
+    fn batch_token_msgs(token_ids: &[&str]) -> Vec<TokenMsg> {
+        token_ids
+            .iter()
+            .map(|token_id| TokenMsg {
+                owner: OWNER.to_string(),
+                token_id: Some(token_id.to_string()),
+                token_uri: None,
+                royalty: None,
+                extension: None,
+                soulbound: false,
+            })
+            .collect()
+    }
+
+    fn mint_batch(
+        contract: &Contract,
+        deps: &mut OwnedDeps<MemoryStorage, MockApi, MockQuerier>,
+        env: Env,
+        caller: &str,
+        tokens: Vec<TokenMsg>,
+    ) -> Result<Response, ContractError> {
+        contract.execute(
+            deps.as_mut(),
+            env,
+            get_mock_info(caller),
+            ExecuteMsg::MintBatch { tokens },
+        )
+    }
+
+    #[test]
+    fn should_mint_batch() {
+        let (mut deps, contract, env, _) = initialize_contract();
+
+        let mint_res = mint_batch(
+            &contract,
+            &mut deps,
+            env,
+            MINTER,
+            batch_token_msgs(&["1", "2", "3"]),
+        )
+        .unwrap();
+
+        assert_eq!(contract.token_count(&deps.storage).unwrap(), 3);
+        insta::assert_json_snapshot!(mint_res);
+    }
+
+    #[test]
+    fn should_fail_mint_batch_when_called_not_by_minter() {
+        let (mut deps, contract, env, _) = initialize_contract();
+
+        let mint_res = mint_batch(
+            &contract,
+            &mut deps,
+            env,
+            STRANGER,
+            batch_token_msgs(&["1"]),
+        )
+        .unwrap_err();
+
+        assert!(matches!(mint_res, ContractError::Unauthorized {}));
+    }
+
+    #[test]
+    fn should_fail_mint_batch_with_invalid_size() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        let too_many: Vec<String> = (0..=MAX_BATCH_SIZE).map(|id| id.to_string()).collect();
+        let too_many: Vec<&str> = too_many.iter().map(String::as_str).collect();
+
+        let empty_res = mint_batch(&contract, &mut deps, env.clone(), MINTER, vec![]).unwrap_err();
+        let too_many_res = mint_batch(
+            &contract,
+            &mut deps,
+            env,
+            MINTER,
+            batch_token_msgs(&too_many),
+        )
+        .unwrap_err();
+
+        assert!(matches!(empty_res, ContractError::InvalidBatchSize { .. }));
+        assert!(matches!(
+            too_many_res,
+            ContractError::InvalidBatchSize { .. }
+        ));
+    }
+
+    #[test]
+    fn should_not_mint_any_token_when_batch_has_duplicates() {
+        let (mut deps, contract, env, _) = initialize_contract();
+
+        let mint_res = mint_batch(
+            &contract,
+            &mut deps,
+            env,
+            MINTER,
+            batch_token_msgs(&["1", "2", "1"]),
+        )
+        .unwrap_err();
+
+        assert!(
+            matches!(mint_res, ContractError::DuplicateTokenId { token_id } if token_id == "1")
+        );
+        assert_eq!(contract.token_count(&deps.storage).unwrap(), 0);
+        assert!(!contract.tokens.has(&deps.storage, "2"));
+    }
+
+    #[test]
+    fn should_not_mint_any_token_when_batch_has_existing_token() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+
+        let mint_res = mint_batch(
+            &contract,
+            &mut deps,
+            env,
+            MINTER,
+            batch_token_msgs(&["new", TOKEN_ID]),
+        )
+        .unwrap_err();
+
+        assert!(matches!(
+            mint_res,
+            ContractError::TokenAlreadyExistsError {}
+        ));
+        assert_eq!(contract.token_count(&deps.storage).unwrap(), 1);
+        assert!(!contract.tokens.has(&deps.storage, "new"));
+    }
+
+    #[test]
+    fn should_mint_with_sequential_token_ids() {
+        let (mut deps, contract, env, _) = initialize_contract_with_msg(InstantiateMsg {
+            auto_token_id: true,
+            ..get_default_instantiate_msg()
+        });
+        let token = TokenMsg {
+            token_id: None,
+            ..batch_token_msgs(&[""]).remove(0)
+        };
+
+        let first_res = contract
+            .mint(
+                deps.as_mut(),
+                mock_env(),
+                get_mock_info(MINTER),
+                token.clone(),
+            )
+            .unwrap();
+        let batch_res = mint_batch(
+            &contract,
+            &mut deps,
+            env,
+            MINTER,
+            vec![token.clone(), token],
+        )
+        .unwrap();
+
+        let first: MintResponse = from_binary(&first_res.data.unwrap()).unwrap();
+        let batch: MintBatchResponse = from_binary(&batch_res.data.unwrap()).unwrap();
+        assert_eq!(first.token_id, "1");
+        assert_eq!(batch.token_ids, vec!["2", "3"]);
+        assert!(contract.tokens.has(&deps.storage, "3"));
+    }
+
+    #[test]
+    fn should_fail_mint_with_token_id_when_ids_are_sequential() {
+        let (mut deps, contract, env, _) = initialize_contract_with_msg(InstantiateMsg {
+            auto_token_id: true,
+            ..get_default_instantiate_msg()
+        });
+
+        let mint_res = mint_token(&contract, &mut deps, env, MINTER, TOKEN_ID).unwrap_err();
+
+        assert!(matches!(mint_res, ContractError::UnexpectedTokenId {}));
+    }
+
+    #[test]
+    fn should_fail_mint_without_token_id() {
+        let (mut deps, contract, _, _) = initialize_contract();
+        let token = TokenMsg {
+            token_id: None,
+            ..batch_token_msgs(&[""]).remove(0)
+        };
+
+        let mint_res = contract
+            .mint(deps.as_mut(), mock_env(), get_mock_info(MINTER), token)
+            .unwrap_err();
+
+        assert!(matches!(mint_res, ContractError::MissingTokenId {}));
+    }
+
+    fn initialize_contract_with_token_id_rules(
+        token_id_rules: TokenIdRules,
+    ) -> (
+        OwnedDeps<MemoryStorage, MockApi, MockQuerier>,
+        Contract<'static>,
+        Env,
+        Response,
+    ) {
+        initialize_contract_with_msg(InstantiateMsg {
+            token_id_rules: Some(token_id_rules),
+            ..get_default_instantiate_msg()
+        })
+    }
+
+    fn assert_invalid_token_id(err: ContractError, expected: &str) {
+        assert_eq!(err.to_string(), format!("Invalid token id: {expected}"));
+        match err {
+            ContractError::InvalidTokenId { reason } => assert_eq!(reason, expected),
+            err => panic!("unexpected error: {err:?}"),
+        }
+    }
+
+    #[test]
+    fn should_fail_mint_with_empty_token_id() {
+        let (mut deps, contract, env, _) = initialize_contract();
+
+        let mint_res = mint_token(&contract, &mut deps, env, MINTER, "").unwrap_err();
+
+        assert_invalid_token_id(mint_res, "empty");
+    }
+
+    #[test]
+    fn should_fail_mint_with_too_long_token_id() {
+        let (mut deps, contract, env, _) = initialize_contract_with_token_id_rules(TokenIdRules {
+            max_length: 4,
+            ..TokenIdRules::default()
+        });
+
+        mint_token(&contract, &mut deps, env.clone(), MINTER, "four").unwrap();
+        let mint_res = mint_token(&contract, &mut deps, env, MINTER, "fives").unwrap_err();
+
+        assert_invalid_token_id(mint_res, "longer than 4 characters");
+    }
+
+    #[test]
+    fn should_fail_instantiate_with_zero_token_id_length() {
+        let mut deps = mock_dependencies();
+        let contract: Contract = Contract::get_contract();
+
+        let init_result = contract
+            .instantiate(
+                deps.as_mut(),
+                mock_env(),
+                get_mock_info(OWNER),
+                InstantiateMsg {
+                    token_id_rules: Some(TokenIdRules {
+                        max_length: 0,
+                        ..TokenIdRules::default()
+                    }),
+                    ..get_default_instantiate_msg()
+                },
+            )
+            .unwrap_err();
+
+        assert!(matches!(init_result, ContractError::InvalidTokenIdRules {}))
+    }
+
+    #[test]
+    fn should_fail_mint_with_control_character_in_token_id() {
+        let (mut deps, contract, env, _) = initialize_contract();
+
+        mint_token(&contract, &mut deps, env.clone(), MINTER, "token #1").unwrap();
+        let mint_res = mint_token(&contract, &mut deps, env, MINTER, "token\n2").unwrap_err();
+
+        assert_invalid_token_id(mint_res, "character '\\n' is not allowed");
+    }
+
+    #[test]
+    fn should_fail_mint_with_token_id_outside_url_safe_charset() {
+        let (mut deps, contract, env, _) = initialize_contract_with_token_id_rules(TokenIdRules {
+            charset: TokenIdCharset::UrlSafe,
+            ..TokenIdRules::default()
+        });
+
+        mint_token(&contract, &mut deps, env.clone(), MINTER, "Token-1_a.b~c").unwrap();
+        let mint_res = mint_token(&contract, &mut deps, env, MINTER, "token/2").unwrap_err();
+
+        assert_invalid_token_id(mint_res, "character '/' is not allowed");
+    }
+
+    #[test]
+    fn should_fail_mint_with_non_numeric_token_id_in_numeric_only_mode() {
+        let (mut deps, contract, env, _) = initialize_contract_with_token_id_rules(TokenIdRules {
+            numeric_only: true,
+            ..TokenIdRules::default()
+        });
+
+        mint_token(&contract, &mut deps, env.clone(), MINTER, "0").unwrap();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, "42").unwrap();
+        let letters_res = mint_token(&contract, &mut deps, env.clone(), MINTER, "4a").unwrap_err();
+        let sign_res = mint_token(&contract, &mut deps, env.clone(), MINTER, "-4").unwrap_err();
+        let leading_zero_res = mint_token(&contract, &mut deps, env, MINTER, "042").unwrap_err();
+
+        assert_invalid_token_id(letters_res, "not a number");
+        assert_invalid_token_id(sign_res, "not a number");
+        assert_invalid_token_id(leading_zero_res, "leading zero");
+    }
+
+    #[test]
+    fn should_validate_token_ids_in_batch_mint() {
+        let (mut deps, contract, env, _) = initialize_contract_with_token_id_rules(TokenIdRules {
+            numeric_only: true,
+            ..TokenIdRules::default()
+        });
+
+        let mint_res = mint_batch(
+            &contract,
+            &mut deps,
+            env,
+            MINTER,
+            batch_token_msgs(&["1", "two"]),
+        )
+        .unwrap_err();
+
+        assert_invalid_token_id(mint_res, "not a number");
+        assert!(!contract.tokens.has(&deps.storage, "1"));
+    }
+
+    #[test]
+    fn should_fail_mint_when_max_supply_is_reached() {
+        let (mut deps, contract, env, _) = initialize_contract_with_msg(InstantiateMsg {
+            max_supply: Some(2),
+            ..get_default_instantiate_msg()
+        });
+        mint_token(&contract, &mut deps, env.clone(), MINTER, "1").unwrap();
+
+        let batch_res = mint_batch(
+            &contract,
+            &mut deps,
+            env.clone(),
+            MINTER,
+            batch_token_msgs(&["2", "3"]),
+        )
+        .unwrap_err();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, "2").unwrap();
+        let mint_res = mint_token(&contract, &mut deps, env, MINTER, "3").unwrap_err();
+
+        assert!(matches!(batch_res, ContractError::MaxSupplyReached {}));
+        assert!(matches!(mint_res, ContractError::MaxSupplyReached {}));
+    }
+
+    #[test]
+    fn should_only_lower_max_supply() {
+        let (mut deps, contract, env, _) = initialize_contract_with_msg(InstantiateMsg {
+            max_supply: Some(10),
+            ..get_default_instantiate_msg()
+        });
+        mint_token(&contract, &mut deps, env.clone(), MINTER, "1").unwrap();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, "2").unwrap();
+        let mut update_max_supply = |max_supply| {
+            contract.execute(
+                deps.as_mut(),
+                env.clone(),
+                get_mock_info(OWNER),
+                ExecuteMsg::UpdateMaxSupply { max_supply },
+            )
+        };
+
+        let raise_res = update_max_supply(11).unwrap_err();
+        let below_supply_res = update_max_supply(1).unwrap_err();
+        update_max_supply(2).unwrap();
+
+        assert!(matches!(raise_res, ContractError::CannotRaiseMaxSupply {}));
+        assert!(matches!(
+            below_supply_res,
+            ContractError::InvalidMaxSupply {}
+        ));
+        assert_eq!(
+            contract
+                .contract_info
+                .load(&deps.storage)
+                .unwrap()
+                .max_supply,
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn should_fail_update_max_supply_when_called_not_by_owner() {
+        let (mut deps, contract, env, _) = initialize_contract();
+
+        let update_res = contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(MINTER),
+                ExecuteMsg::UpdateMaxSupply { max_supply: 1 },
+            )
+            .unwrap_err();
+
+        assert!(matches!(update_res, ContractError::Unauthorized {}));
+    }
+
+    fn sale_config() -> SaleConfig {
+        SaleConfig {
+            price: coin(100, "umlg"),
+            per_tx_limit: 3,
+            start_time: mock_env().block.time,
+        }
+    }
+
+    fn initialize_contract_with_sale() -> (
+        OwnedDeps<MemoryStorage, MockApi, MockQuerier>,
+        Contract<'static>,
+        Env,
+        Response,
+    ) {
+        initialize_contract_with_msg(InstantiateMsg {
+            auto_token_id: true,
+            sale: Some(sale_config()),
+            ..get_default_instantiate_msg()
+        })
+    }
+
+    fn public_mint(
+        contract: &Contract,
+        deps: &mut OwnedDeps<MemoryStorage, MockApi, MockQuerier>,
+        env: Env,
+        funds: &[Coin],
+        quantity: Option<u32>,
+    ) -> Result<Response, ContractError> {
+        contract.execute(
+            deps.as_mut(),
+            env,
+            mock_info(STRANGER, funds),
+            ExecuteMsg::PublicMint {
+                owner: STRANGER.to_string(),
+                quantity,
+            },
+        )
+    }
+
+    #[test]
+    fn should_public_mint_for_exact_payment() {
+        let (mut deps, contract, env, _) = initialize_contract_with_sale();
+
+        let mint_res =
+            public_mint(&contract, &mut deps, env, &coins(200, "umlg"), Some(2)).unwrap();
+
+        let minted: MintBatchResponse = from_binary(mint_res.data.as_ref().unwrap()).unwrap();
+        assert_eq!(minted.token_ids, vec!["1", "2"]);
+        assert_eq!(
+            contract.tokens.load(&deps.storage, "2").unwrap().owner,
+            STRANGER
+        );
+        insta::assert_json_snapshot!(mint_res);
+    }
+
+    #[test]
+    fn should_fail_public_mint_with_wrong_payment() {
+        let (mut deps, contract, env, _) = initialize_contract_with_sale();
+
+        for funds in [
+            vec![],
+            coins(99, "umlg"),
+            coins(200, "umlg"),
+            coins(100, "uatom"),
+            vec![coin(100, "umlg"), coin(1, "uatom")],
+        ] {
+            let mint_res =
+                public_mint(&contract, &mut deps, env.clone(), &funds, None).unwrap_err();
+
+            assert!(matches!(
+                mint_res,
+                ContractError::InvalidPayment { expected } if expected == coin(100, "umlg")
+            ));
+        }
+    }
+
+    #[test]
+    fn should_fail_public_mint_over_per_tx_limit() {
+        let (mut deps, contract, env, _) = initialize_contract_with_sale();
+
+        let mint_res =
+            public_mint(&contract, &mut deps, env, &coins(400, "umlg"), Some(4)).unwrap_err();
+
+        assert!(matches!(
+            mint_res,
+            ContractError::InvalidMintQuantity { max: 3 }
+        ));
+    }
+
+    #[test]
+    fn should_fail_public_mint_before_start_or_when_closed() {
+        let (mut deps, contract, mut env, _) = initialize_contract_with_sale();
+        env.block.time = env.block.time.minus_seconds(1);
+
+        let early_res =
+            public_mint(&contract, &mut deps, env.clone(), &coins(100, "umlg"), None).unwrap_err();
+        contract
+            .execute(
+                deps.as_mut(),
+                env.clone(),
+                get_mock_info(OWNER),
+                ExecuteMsg::UpdateSale { sale: None },
+            )
+            .unwrap();
+        let closed_res =
+            public_mint(&contract, &mut deps, env, &coins(100, "umlg"), None).unwrap_err();
+
+        assert!(matches!(early_res, ContractError::SaleNotStarted {}));
+        assert!(matches!(closed_res, ContractError::SaleNotActive {}));
+    }
+
+    #[test]
+    fn should_fail_sale_without_sequential_token_ids() {
+        let (mut deps, contract, env, _) = initialize_contract();
+
+        let update_res = contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(OWNER),
+                ExecuteMsg::UpdateSale {
+                    sale: Some(sale_config()),
+                },
+            )
+            .unwrap_err();
+
+        assert!(matches!(update_res, ContractError::InvalidSaleConfig {}));
+    }
+
+    #[test]
+    fn should_withdraw_proceeds() {
+        let (mut deps, contract, env, _) = initialize_contract_with_sale();
+        deps.querier
+            .update_balance(env.contract.address.clone(), coins(300, "umlg"));
+        let withdraw = |deps: &mut OwnedDeps<MemoryStorage, MockApi, MockQuerier>,
+                        caller: &str,
+                        amount: u128| {
+            contract.execute(
+                deps.as_mut(),
+                env.clone(),
+                get_mock_info(caller),
+                ExecuteMsg::Withdraw {
+                    recipient: OWNER.to_string(),
+                    amount: coin(amount, "umlg"),
+                },
+            )
+        };
+
+        let stranger_res = withdraw(&mut deps, STRANGER, 300).unwrap_err();
+        let excess_res = withdraw(&mut deps, OWNER, 301).unwrap_err();
+        let withdraw_res = withdraw(&mut deps, OWNER, 300).unwrap();
+
+        assert!(matches!(stranger_res, ContractError::Unauthorized {}));
+        assert!(matches!(excess_res, ContractError::InsufficientBalance {}));
+        assert_eq!(
+            withdraw_res.messages[0].msg,
+            BankMsg::Send {
+                to_address: OWNER.to_string(),
+                amount: coins(300, "umlg"),
+            }
+            .into()
+        );
+    }
+
+    const ALLOWLIST: [(&str, u32); 3] = [(STRANGER, 2), (SPENDER, 1), (OWNER, 1)];
+
+    fn initialize_contract_with_allowlist() -> (
+        OwnedDeps<MemoryStorage, MockApi, MockQuerier>,
+        Contract<'static>,
+        Env,
+        MerkleTree,
+    ) {
+        let (mut deps, contract, env, _) = initialize_contract_with_msg(InstantiateMsg {
+            auto_token_id: true,
+            ..get_default_instantiate_msg()
+        });
+        let tree = MerkleTree::from_allowlist(ALLOWLIST);
+        contract
+            .execute(
+                deps.as_mut(),
+                env.clone(),
+                get_mock_info(OWNER),
+                ExecuteMsg::SetMerkleRoot {
+                    root: tree.root().map(|root| root.into()),
+                },
+            )
+            .unwrap();
+
+        (deps, contract, env, tree)
+    }
+
+    fn allowlist_mint(
+        contract: &Contract,
+        deps: &mut OwnedDeps<MemoryStorage, MockApi, MockQuerier>,
+        env: Env,
+        proof: Vec<[u8; 32]>,
+        allocation: u32,
+    ) -> Result<Response, ContractError> {
+        contract.execute(
+            deps.as_mut(),
+            env,
+            get_mock_info(STRANGER),
+            ExecuteMsg::AllowlistMint {
+                proof: proof.into_iter().map(|hash| hash.into()).collect(),
+                allocation,
+                quantity: None,
+            },
+        )
+    }
+
+    #[test]
+    fn should_allowlist_mint_up_to_allocation() {
+        let (mut deps, contract, env, tree) = initialize_contract_with_allowlist();
+        let proof = tree.proof(0).unwrap();
+
+        let mint_res = allowlist_mint(&contract, &mut deps, env.clone(), proof.clone(), 2).unwrap();
+        allowlist_mint(&contract, &mut deps, env.clone(), proof.clone(), 2).unwrap();
+        let exceeded_res = allowlist_mint(&contract, &mut deps, env, proof, 2).unwrap_err();
+
+        assert!(matches!(
+            exceeded_res,
+            ContractError::AllocationExceeded { remaining: 0 }
+        ));
+        assert_eq!(
+            contract
+                .allowlist_minted
+                .load(
+                    &deps.storage,
+                    (tree.root().unwrap().as_slice(), &Addr::unchecked(STRANGER))
+                )
+                .unwrap(),
+            2
+        );
+        insta::assert_json_snapshot!(mint_res);
+    }
+
+    #[test]
+    fn should_fail_allowlist_mint_with_wrong_allocation_or_proof() {
+        let (mut deps, contract, env, tree) = initialize_contract_with_allowlist();
+
+        let allocation_res =
+            allowlist_mint(&contract, &mut deps, env.clone(), tree.proof(0).unwrap(), 3)
+                .unwrap_err();
+        let proof_res =
+            allowlist_mint(&contract, &mut deps, env, tree.proof(1).unwrap(), 2).unwrap_err();
+
+        assert!(matches!(
+            allocation_res,
+            ContractError::InvalidMerkleProof {}
+        ));
+        assert!(matches!(proof_res, ContractError::InvalidMerkleProof {}));
+    }
+
+    #[test]
+    fn should_fail_allowlist_mint_with_funds() {
+        let (mut deps, contract, env, tree) = initialize_contract_with_allowlist();
+
+        let mint_res = contract
+            .execute(
+                deps.as_mut(),
+                env,
+                mock_info(STRANGER, &coins(100, "umlg")),
+                ExecuteMsg::AllowlistMint {
+                    proof: tree
+                        .proof(0)
+                        .unwrap()
+                        .into_iter()
+                        .map(|hash| hash.into())
+                        .collect(),
+                    allocation: 2,
+                    quantity: None,
+                },
+            )
+            .unwrap_err();
+
+        assert!(matches!(mint_res, ContractError::InvalidPayment { .. }));
+    }
+
+    #[test]
+    fn should_fail_allowlist_mint_without_root() {
+        let (mut deps, contract, env, tree) = initialize_contract_with_allowlist();
+        contract
+            .execute(
+                deps.as_mut(),
+                env.clone(),
+                get_mock_info(OWNER),
+                ExecuteMsg::SetMerkleRoot { root: None },
+            )
+            .unwrap();
+
+        let mint_res =
+            allowlist_mint(&contract, &mut deps, env, tree.proof(0).unwrap(), 2).unwrap_err();
+
+        assert!(matches!(mint_res, ContractError::AllowlistNotActive {}));
+    }
+
+    #[test]
+    fn should_fail_set_merkle_root_with_invalid_root() {
+        let (mut deps, contract, env, _) = initialize_contract_with_allowlist();
+
+        let set_res = contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(OWNER),
+                ExecuteMsg::SetMerkleRoot {
+                    root: Some(vec![0u8; 31].into()),
+                },
+            )
+            .unwrap_err();
+
+        assert!(matches!(set_res, ContractError::InvalidMerkleRoot {}));
+    }
+
+    #[test]
+    fn should_fail_set_merkle_root_without_auto_token_id() {
+        let (mut deps, contract, env, _) = initialize_contract();
+
+        let set_res = contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(OWNER),
+                ExecuteMsg::SetMerkleRoot {
+                    root: Some([0u8; 32].into()),
+                },
+            )
+            .unwrap_err();
+
+        assert!(matches!(set_res, ContractError::AutoTokenIdRequired {}));
+    }
+
+    fn mint_phases(tree: &MerkleTree, start: Timestamp) -> Vec<MintPhase> {
+        vec![
+            MintPhase {
+                name: "allowlist".to_string(),
+                start_time: start,
+                end_time: start.plus_seconds(100),
+                price: coin(50, "umlg"),
+                per_wallet_limit: 5,
+                eligibility: Eligibility::Allowlist {
+                    root: tree.root().unwrap().into(),
+                },
+            },
+            MintPhase {
+                name: "public".to_string(),
+                start_time: start.plus_seconds(100),
+                end_time: start.plus_seconds(200),
+                price: coin(100, "umlg"),
+                per_wallet_limit: 1,
+                eligibility: Eligibility::Public,
+            },
+        ]
+    }
+
+    fn update_phases(
+        contract: &Contract,
+        deps: &mut OwnedDeps<MemoryStorage, MockApi, MockQuerier>,
+        env: Env,
+        phases: Vec<MintPhase>,
+    ) -> Result<Response, ContractError> {
+        contract.execute(
+            deps.as_mut(),
+            env,
+            get_mock_info(OWNER),
+            ExecuteMsg::UpdatePhases { phases },
+        )
+    }
+
+    fn phase_mint(
+        contract: &Contract,
+        deps: &mut OwnedDeps<MemoryStorage, MockApi, MockQuerier>,
+        env: Env,
+        funds: &[Coin],
+        proof: Option<Vec<[u8; 32]>>,
+    ) -> Result<Response, ContractError> {
+        contract.execute(
+            deps.as_mut(),
+            env,
+            mock_info(STRANGER, funds),
+            ExecuteMsg::PhaseMint {
+                quantity: None,
+                proof: proof.map(|proof| proof.into_iter().map(|hash| hash.into()).collect()),
+                allocation: Some(2),
+            },
+        )
+    }
+
+    #[test]
+    fn should_mint_on_terms_of_current_phase() {
+        let (mut deps, contract, mut env, tree) = initialize_contract_with_allowlist();
+        let start = env.block.time.plus_seconds(10);
+        update_phases(&contract, &mut deps, env.clone(), mint_phases(&tree, start)).unwrap();
+        env.block.time = start;
+        let proof = Some(tree.proof(0).unwrap());
+
+        let mint_res = phase_mint(
+            &contract,
+            &mut deps,
+            env.clone(),
+            &coins(50, "umlg"),
+            proof.clone(),
+        )
+        .unwrap();
+        phase_mint(
+            &contract,
+            &mut deps,
+            env.clone(),
+            &coins(50, "umlg"),
+            proof.clone(),
+        )
+        .unwrap();
+        // the allocation of 2 is below the limit of the phase
+        let allocation_res =
+            phase_mint(&contract, &mut deps, env.clone(), &coins(50, "umlg"), proof).unwrap_err();
+
+        env.block.time = env.block.time.plus_seconds(150);
+        let price_res =
+            phase_mint(&contract, &mut deps, env.clone(), &coins(50, "umlg"), None).unwrap_err();
+        phase_mint(&contract, &mut deps, env.clone(), &coins(100, "umlg"), None).unwrap();
+        let limit_res =
+            phase_mint(&contract, &mut deps, env.clone(), &coins(100, "umlg"), None).unwrap_err();
+
+        env.block.time = env.block.time.plus_seconds(100);
+        let closed_res =
+            phase_mint(&contract, &mut deps, env, &coins(100, "umlg"), None).unwrap_err();
+
+        assert!(matches!(
+            allocation_res,
+            ContractError::AllocationExceeded { remaining: 0 }
+        ));
+        assert!(matches!(price_res, ContractError::InvalidPayment { .. }));
+        assert!(matches!(
+            limit_res,
+            ContractError::AllocationExceeded { remaining: 0 }
+        ));
+        assert!(matches!(closed_res, ContractError::NoActivePhase {}));
+        assert_eq!(contract.token_count(&deps.storage).unwrap(), 3);
+        insta::assert_json_snapshot!(mint_res);
+    }
+
+    #[test]
+    fn should_fail_phase_mint_without_allowlist_proof() {
+        let (mut deps, contract, mut env, tree) = initialize_contract_with_allowlist();
+        let start = env.block.time.plus_seconds(10);
+        update_phases(&contract, &mut deps, env.clone(), mint_phases(&tree, start)).unwrap();
+        env.block.time = start;
+
+        let mint_res = phase_mint(&contract, &mut deps, env, &coins(50, "umlg"), None).unwrap_err();
+
+        assert!(matches!(mint_res, ContractError::InvalidMerkleProof {}));
+    }
+
+    #[test]
+    fn should_share_allocation_between_allowlist_mint_and_phases() {
+        let (mut deps, contract, mut env, tree) = initialize_contract_with_allowlist();
+        let proof = tree.proof(0).unwrap();
+        allowlist_mint(&contract, &mut deps, env.clone(), proof.clone(), 2).unwrap();
+        allowlist_mint(&contract, &mut deps, env.clone(), proof.clone(), 2).unwrap();
+
+        let start = env.block.time.plus_seconds(10);
+        update_phases(&contract, &mut deps, env.clone(), mint_phases(&tree, start)).unwrap();
+        env.block.time = start;
+        let mint_res =
+            phase_mint(&contract, &mut deps, env, &coins(50, "umlg"), Some(proof)).unwrap_err();
+
+        assert!(matches!(
+            mint_res,
+            ContractError::AllocationExceeded { remaining: 0 }
+        ));
+    }
+
+    #[test]
+    fn should_fail_update_phases_starting_now() {
+        let (mut deps, contract, env, tree) = initialize_contract_with_allowlist();
+
+        let update_res = update_phases(
+            &contract,
+            &mut deps,
+            env.clone(),
+            mint_phases(&tree, env.block.time),
+        )
+        .unwrap_err();
+
+        assert!(matches!(update_res, ContractError::InvalidPhases {}));
+    }
+
+    #[test]
+    fn should_lock_phases_once_started() {
+        let (mut deps, contract, mut env, tree) = initialize_contract_with_allowlist();
+        let start = env.block.time.plus_seconds(10);
+
+        update_phases(&contract, &mut deps, env.clone(), mint_phases(&tree, start)).unwrap();
+        update_phases(&contract, &mut deps, env.clone(), mint_phases(&tree, start)).unwrap();
+        env.block.time = start;
+        let update_res =
+            update_phases(&contract, &mut deps, env, mint_phases(&tree, start)).unwrap_err();
+
+        assert!(matches!(update_res, ContractError::PhasesLocked {}));
+    }
+
+    #[test]
+    fn should_fail_update_overlapping_phases() {
+        let (mut deps, contract, env, tree) = initialize_contract_with_allowlist();
+        let mut phases = mint_phases(&tree, env.block.time.plus_seconds(10));
+        phases[1].start_time = phases[0].end_time.minus_seconds(1);
+
+        let update_res = update_phases(&contract, &mut deps, env, phases).unwrap_err();
+
+        assert!(matches!(update_res, ContractError::InvalidPhases {}));
+    }
+
+    #[test]
+    fn should_fail_nft_transfer_when_called_not_by_owner() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+
+        let transfer_result = contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(STRANGER),
+                ExecuteMsg::TransferNft {
+                    token_id: TOKEN_ID.to_string(),
+                    to: STRANGER.to_string(),
+                },
+            )
+            .unwrap_err();
+
+        assert!(matches!(transfer_result, ContractError::Unauthorized {}))
+    }
+
+    #[test]
+    fn should_transfer_nft() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+
+        let transfer_result = contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(OWNER),
+                ExecuteMsg::TransferNft {
+                    token_id: TOKEN_ID.to_string(),
+                    to: STRANGER.to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(transfer_result.messages.len(), 0);
+        insta::assert_json_snapshot!(contract.tokens.load(&deps.storage, TOKEN_ID).unwrap());
+        insta::assert_json_snapshot!(transfer_result.events);
+    }
+
+    #[test]
+    fn should_fail_nft_send_when_called_not_by_owner() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+
+        let send_result = contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(STRANGER),
+                ExecuteMsg::SendNft {
+                    token_id: TOKEN_ID.to_string(),
+                    contract: STRANGER.to_string(),
+                    msg: to_binary("Hello, it's fail!").unwrap(),
+                },
+            )
+            .unwrap_err();
+
+        assert!(matches!(send_result, ContractError::Unauthorized {}))
+    }
+
+    #[test]
+    fn should_send_nft() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+
+        let send_result = contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(OWNER),
+                ExecuteMsg::SendNft {
+                    token_id: TOKEN_ID.to_string(),
+                    contract: STRANGER.to_string(),
+                    msg: to_binary("Hello, it's success!").unwrap(),
+                },
+            )
+            .unwrap();
 
         insta::assert_json_snapshot!(send_result.messages[0].msg);
         insta::assert_json_snapshot!(contract.tokens.load(&deps.storage, TOKEN_ID).unwrap());
+        insta::assert_json_snapshot!(send_result.events);
+    }
+
+    #[test]
+    fn should_batch_transfer_nft() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, "1").unwrap();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, "2").unwrap();
+
+        let transfer_result = contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(OWNER),
+                ExecuteMsg::BatchTransferNft {
+                    token_ids: vec!["1".to_string(), "2".to_string()],
+                    to: STRANGER.to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(transfer_result.messages.len(), 0);
+        for token_id in ["1", "2"] {
+            let token = contract.tokens.load(&deps.storage, token_id).unwrap();
+            assert_eq!(token.owner, STRANGER);
+        }
+    }
+
+    #[test]
+    fn should_not_transfer_any_nft_when_one_of_batch_is_not_owned() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, "1").unwrap();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, "2").unwrap();
+        contract
+            .execute(
+                deps.as_mut(),
+                env.clone(),
+                get_mock_info(OWNER),
+                ExecuteMsg::TransferNft {
+                    token_id: "2".to_string(),
+                    to: SPENDER.to_string(),
+                },
+            )
+            .unwrap();
+
+        let transfer_result = contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(OWNER),
+                ExecuteMsg::BatchTransferNft {
+                    token_ids: vec!["1".to_string(), "2".to_string()],
+                    to: STRANGER.to_string(),
+                },
+            )
+            .unwrap_err();
+
+        assert!(matches!(transfer_result, ContractError::Unauthorized {}));
+        let token = contract.tokens.load(&deps.storage, "1").unwrap();
+        assert_eq!(token.owner, OWNER);
+    }
+
+    #[test]
+    fn should_fail_batch_transfer_with_duplicate_token_ids() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+
+        let transfer_result = contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(OWNER),
+                ExecuteMsg::BatchTransferNft {
+                    token_ids: vec![TOKEN_ID.to_string(), TOKEN_ID.to_string()],
+                    to: STRANGER.to_string(),
+                },
+            )
+            .unwrap_err();
+
+        assert!(matches!(
+            transfer_result,
+            ContractError::DuplicateTokenId { .. }
+        ));
+    }
+
+    #[test]
+    fn should_batch_send_nft_with_callback_per_token() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, "1").unwrap();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, "2").unwrap();
+
+        let send_result = contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(OWNER),
+                ExecuteMsg::BatchSendNft {
+                    token_ids: vec!["1".to_string(), "2".to_string()],
+                    contract: STRANGER.to_string(),
+                    msg: to_binary("Hello, it's success!").unwrap(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(send_result.messages.len(), 2);
+        insta::assert_json_snapshot!(send_result.messages[1].msg);
+    }
+
+    #[test]
+    fn should_batch_send_nft_with_single_callback_when_receiver_opted_in() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, "1").unwrap();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, "2").unwrap();
+        contract
+            .execute(
+                deps.as_mut(),
+                env.clone(),
+                get_mock_info(STRANGER),
+                ExecuteMsg::SetBatchReceive { enabled: true },
+            )
+            .unwrap();
+
+        let send_result = contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(OWNER),
+                ExecuteMsg::BatchSendNft {
+                    token_ids: vec!["1".to_string(), "2".to_string()],
+                    contract: STRANGER.to_string(),
+                    msg: to_binary("Hello, it's success!").unwrap(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(send_result.messages.len(), 1);
+        insta::assert_json_snapshot!(send_result.messages[0].msg);
+    }
+
+    fn update_token_uri(
+        contract: &Contract,
+        deps: &mut OwnedDeps<MemoryStorage, MockApi, MockQuerier>,
+        env: Env,
+        caller: &str,
+    ) -> Result<Response, ContractError> {
+        contract.execute(
+            deps.as_mut(),
+            env,
+            get_mock_info(caller),
+            ExecuteMsg::UpdateTokenUri {
+                token_id: TOKEN_ID.to_string(),
+                token_uri: Some("ipfs://fixed".to_string()),
+            },
+        )
+    }
+
+    #[test]
+    fn should_update_token_uri() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+
+        let update_res = update_token_uri(&contract, &mut deps, env, MINTER).unwrap();
+
+        insta::assert_json_snapshot!(update_res);
+        let token = contract.tokens.load(&deps.storage, TOKEN_ID).unwrap();
+        assert_eq!(token.token_uri, Some("ipfs://fixed".to_string()));
+    }
+
+    #[test]
+    fn should_fail_update_token_uri_when_called_not_by_minter() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+
+        let update_res = update_token_uri(&contract, &mut deps, env, OWNER).unwrap_err();
+
+        assert!(matches!(update_res, ContractError::Unauthorized {}));
+    }
+
+    #[test]
+    fn should_fail_update_token_uri_when_token_is_frozen() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+        contract
+            .execute(
+                deps.as_mut(),
+                env.clone(),
+                get_mock_info(MINTER),
+                ExecuteMsg::FreezeToken {
+                    token_id: TOKEN_ID.to_string(),
+                },
+            )
+            .unwrap();
+
+        let update_res = update_token_uri(&contract, &mut deps, env, MINTER).unwrap_err();
+
+        assert!(matches!(update_res, ContractError::MetadataFrozen {}));
+    }
+
+    #[test]
+    fn should_fail_update_token_uri_when_metadata_is_frozen() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+        contract
+            .execute(
+                deps.as_mut(),
+                env.clone(),
+                get_mock_info(MINTER),
+                ExecuteMsg::FreezeMetadata {},
+            )
+            .unwrap();
+
+        let update_res = update_token_uri(&contract, &mut deps, env, MINTER).unwrap_err();
+
+        assert!(matches!(update_res, ContractError::MetadataFrozen {}));
+    }
+
+    #[test]
+    fn should_fail_transfer_in_soulbound_collection() {
+        let (mut deps, contract, env, _) = initialize_contract_with_msg(InstantiateMsg {
+            soulbound: true,
+            ..get_default_instantiate_msg()
+        });
+        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+
+        let transfer_result = contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(OWNER),
+                ExecuteMsg::TransferNft {
+                    token_id: TOKEN_ID.to_string(),
+                    to: STRANGER.to_string(),
+                },
+            )
+            .unwrap_err();
+
+        assert!(matches!(transfer_result, ContractError::NonTransferable {}));
+    }
+
+    #[test]
+    fn should_fail_send_of_soulbound_token() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        let token = TokenMsg {
+            soulbound: true,
+            ..batch_token_msgs(&[TOKEN_ID]).remove(0)
+        };
+        contract
+            .mint(deps.as_mut(), mock_env(), get_mock_info(MINTER), token)
+            .unwrap();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, "transferable").unwrap();
+
+        let send_result = contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(OWNER),
+                ExecuteMsg::BatchSendNft {
+                    token_ids: vec!["transferable".to_string(), TOKEN_ID.to_string()],
+                    contract: STRANGER.to_string(),
+                    msg: to_binary("Hello, it's fail!").unwrap(),
+                },
+            )
+            .unwrap_err();
+
+        assert!(matches!(send_result, ContractError::NonTransferable {}));
+    }
+
+    #[test]
+    fn should_burn_soulbound_token_by_owner_and_minter() {
+        let (mut deps, contract, env, _) = initialize_contract_with_msg(InstantiateMsg {
+            soulbound: true,
+            ..get_default_instantiate_msg()
+        });
+        mint_token(&contract, &mut deps, env.clone(), MINTER, "1").unwrap();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, "2").unwrap();
+        let mut burn = |caller, token_id: &str| {
+            contract.execute(
+                deps.as_mut(),
+                env.clone(),
+                get_mock_info(caller),
+                ExecuteMsg::Burn {
+                    token_id: token_id.to_string(),
+                },
+            )
+        };
+
+        let stranger_res = burn(STRANGER, "1").unwrap_err();
+        burn(OWNER, "1").unwrap();
+        burn(MINTER, "2").unwrap();
+
+        assert!(matches!(stranger_res, ContractError::Unauthorized {}));
+        assert_eq!(contract.token_count(&deps.storage).unwrap(), 0);
+    }
+
+    fn pause(
+        contract: &Contract,
+        deps: &mut OwnedDeps<MemoryStorage, MockApi, MockQuerier>,
+        env: Env,
+        caller: &str,
+        scope: Option<PauseScope>,
+    ) -> Result<Response, ContractError> {
+        contract.execute(
+            deps.as_mut(),
+            env,
+            get_mock_info(caller),
+            ExecuteMsg::Pause { scope },
+        )
+    }
+
+    #[test]
+    fn should_fail_pause_when_called_not_by_owner() {
+        let (mut deps, contract, env, _) = initialize_contract();
+
+        let pause_res = pause(&contract, &mut deps, env, MINTER, None).unwrap_err();
+
+        assert!(matches!(pause_res, ContractError::Unauthorized {}));
+    }
+
+    #[test]
+    fn should_pause_minting_only() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+        pause(
+            &contract,
+            &mut deps,
+            env.clone(),
+            OWNER,
+            Some(PauseScope::Minting),
+        )
+        .unwrap();
+
+        let mint_res = mint_token(&contract, &mut deps, env.clone(), MINTER, "2").unwrap_err();
+        contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(OWNER),
+                ExecuteMsg::TransferNft {
+                    token_id: TOKEN_ID.to_string(),
+                    to: STRANGER.to_string(),
+                },
+            )
+            .unwrap();
+
+        assert!(matches!(mint_res, ContractError::Paused {}));
+    }
+
+    #[test]
+    fn should_pause_everything_until_unpaused() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+        pause(&contract, &mut deps, env.clone(), OWNER, None).unwrap();
+
+        let transfer_msg = ExecuteMsg::TransferNft {
+            token_id: TOKEN_ID.to_string(),
+            to: STRANGER.to_string(),
+        };
+        let transfer_res = contract
+            .execute(
+                deps.as_mut(),
+                env.clone(),
+                get_mock_info(OWNER),
+                transfer_msg.clone(),
+            )
+            .unwrap_err();
+        let approve_res = contract
+            .execute(
+                deps.as_mut(),
+                env.clone(),
+                get_mock_info(OWNER),
+                ExecuteMsg::ApproveAll {
+                    operator: SPENDER.to_string(),
+                    expires: None,
+                },
+            )
+            .unwrap_err();
+        contract
+            .execute(
+                deps.as_mut(),
+                env.clone(),
+                get_mock_info(OWNER),
+                ExecuteMsg::Unpause {},
+            )
+            .unwrap();
+
+        assert!(matches!(transfer_res, ContractError::Paused {}));
+        assert!(matches!(approve_res, ContractError::Paused {}));
+        contract
+            .execute(deps.as_mut(), env, get_mock_info(OWNER), transfer_msg)
+            .unwrap();
+    }
+
+    #[test]
+    fn should_update_minter_and_withdraw_while_paused() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        deps.querier
+            .update_balance(env.contract.address.clone(), coins(300, "umlg"));
+        pause(&contract, &mut deps, env.clone(), OWNER, None).unwrap();
+
+        contract
+            .execute(
+                deps.as_mut(),
+                env.clone(),
+                get_mock_info(OWNER),
+                ExecuteMsg::UpdateMinter {
+                    minter: STRANGER.to_string(),
+                },
+            )
+            .unwrap();
+        contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(OWNER),
+                ExecuteMsg::Withdraw {
+                    recipient: OWNER.to_string(),
+                    amount: coin(300, "umlg"),
+                },
+            )
+            .unwrap();
+
+        let minter = contract.get_minter(deps.as_ref()).unwrap();
+        assert_eq!(minter.minter, STRANGER);
+    }
+
+    #[test]
+    fn should_fail_approve_when_called_not_by_owner() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+
+        let approve_result = contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(STRANGER),
+                ExecuteMsg::Approve {
+                    spender: STRANGER.to_string(),
+                    token_id: TOKEN_ID.to_string(),
+                    expires: None,
+                },
+            )
+            .unwrap_err();
+
+        assert!(matches!(approve_result, ContractError::Unauthorized {}))
+    }
+
+    #[test]
+    fn should_fail_approve_with_expired_expiration() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+
+        let approve_result = contract
+            .execute(
+                deps.as_mut(),
+                env.clone(),
+                get_mock_info(OWNER),
+                ExecuteMsg::Approve {
+                    spender: SPENDER.to_string(),
+                    token_id: TOKEN_ID.to_string(),
+                    expires: Some(Expiration::AtHeight(env.block.height)),
+                },
+            )
+            .unwrap_err();
+
+        assert!(matches!(approve_result, ContractError::Expired {}))
+    }
+
+    #[test]
+    fn should_transfer_nft_by_approved_spender_and_clear_approvals() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+        approve_token(&contract, &mut deps, env.clone(), None);
+
+        contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(SPENDER),
+                ExecuteMsg::TransferNft {
+                    token_id: TOKEN_ID.to_string(),
+                    to: STRANGER.to_string(),
+                },
+            )
+            .unwrap();
+
+        let token = contract.tokens.load(&deps.storage, TOKEN_ID).unwrap();
+        assert_eq!(token.owner, STRANGER);
+        assert!(token.approvals.is_empty());
+    }
+
+    #[test]
+    fn should_fail_nft_transfer_when_approval_expired() {
+        let (mut deps, contract, mut env, _) = initialize_contract();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+        approve_token(
+            &contract,
+            &mut deps,
+            env.clone(),
+            Some(Expiration::AtHeight(env.block.height + 1)),
+        );
+
+        env.block.height += 1;
+        let transfer_result = contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(SPENDER),
+                ExecuteMsg::TransferNft {
+                    token_id: TOKEN_ID.to_string(),
+                    to: SPENDER.to_string(),
+                },
+            )
+            .unwrap_err();
+
+        assert!(matches!(transfer_result, ContractError::Unauthorized {}))
+    }
+
+    #[test]
+    fn should_fail_nft_send_after_revoke() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+        approve_token(&contract, &mut deps, env.clone(), None);
+
+        contract
+            .execute(
+                deps.as_mut(),
+                env.clone(),
+                get_mock_info(OWNER),
+                ExecuteMsg::Revoke {
+                    spender: SPENDER.to_string(),
+                    token_id: TOKEN_ID.to_string(),
+                },
+            )
+            .unwrap();
+
+        let send_result = contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(SPENDER),
+                ExecuteMsg::SendNft {
+                    token_id: TOKEN_ID.to_string(),
+                    contract: STRANGER.to_string(),
+                    msg: to_binary("Hello, it's fail!").unwrap(),
+                },
+            )
+            .unwrap_err();
+
+        assert!(matches!(send_result, ContractError::Unauthorized {}))
+    }
+
+    #[test]
+    fn should_transfer_nft_by_operator() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+        approve_all(&contract, &mut deps, env.clone(), None);
+
+        contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(SPENDER),
+                ExecuteMsg::TransferNft {
+                    token_id: TOKEN_ID.to_string(),
+                    to: STRANGER.to_string(),
+                },
+            )
+            .unwrap();
+
+        let token = contract.tokens.load(&deps.storage, TOKEN_ID).unwrap();
+        assert_eq!(token.owner, STRANGER);
+    }
+
+    #[test]
+    fn should_approve_token_by_operator() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+        approve_all(&contract, &mut deps, env.clone(), None);
+
+        contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(SPENDER),
+                ExecuteMsg::Approve {
+                    spender: STRANGER.to_string(),
+                    token_id: TOKEN_ID.to_string(),
+                    expires: None,
+                },
+            )
+            .unwrap();
+
+        let token = contract.tokens.load(&deps.storage, TOKEN_ID).unwrap();
+        assert_eq!(token.approvals[0].spender, STRANGER);
+    }
+
+    #[test]
+    fn should_fail_nft_transfer_when_operator_expired() {
+        let (mut deps, contract, mut env, _) = initialize_contract();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+        approve_all(
+            &contract,
+            &mut deps,
+            env.clone(),
+            Some(Expiration::AtTime(env.block.time.plus_seconds(10))),
+        );
+
+        env.block.time = env.block.time.plus_seconds(10);
+        let transfer_result = contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(SPENDER),
+                ExecuteMsg::TransferNft {
+                    token_id: TOKEN_ID.to_string(),
+                    to: SPENDER.to_string(),
+                },
+            )
+            .unwrap_err();
+
+        assert!(matches!(transfer_result, ContractError::Unauthorized {}))
+    }
+
+    #[test]
+    fn should_fail_nft_transfer_after_revoke_all() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+        approve_all(&contract, &mut deps, env.clone(), None);
+
+        contract
+            .execute(
+                deps.as_mut(),
+                env.clone(),
+                get_mock_info(OWNER),
+                ExecuteMsg::RevokeAll {
+                    operator: SPENDER.to_string(),
+                },
+            )
+            .unwrap();
+
+        let transfer_result = contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(SPENDER),
+                ExecuteMsg::TransferNft {
+                    token_id: TOKEN_ID.to_string(),
+                    to: SPENDER.to_string(),
+                },
+            )
+            .unwrap_err();
+
+        assert!(matches!(transfer_result, ContractError::Unauthorized {}))
+    }
+
+    #[test]
+    fn should_fail_burn_when_called_not_by_owner() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+
+        let burn_result = contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(STRANGER),
+                ExecuteMsg::Burn {
+                    token_id: TOKEN_ID.to_string(),
+                },
+            )
+            .unwrap_err();
+
+        assert!(matches!(burn_result, ContractError::Unauthorized {}))
+    }
+
+    #[test]
+    fn should_burn_nft() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, "2").unwrap();
+
+        contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(OWNER),
+                ExecuteMsg::Burn {
+                    token_id: TOKEN_ID.to_string(),
+                },
+            )
+            .unwrap();
+
+        assert!(!contract.tokens.has(&deps.storage, TOKEN_ID));
+        assert_eq!(contract.get_num_tokens(deps.as_ref()).unwrap().count, 1);
+        let owner_tokens = contract
+            .get_owner_tokens(deps.as_ref(), OWNER.to_string(), None, None)
+            .unwrap();
+        assert_eq!(owner_tokens.tokens.len(), 1);
+        assert_eq!(owner_tokens.tokens, vec!["2"]);
+    }
+
+    #[test]
+    fn should_burn_nft_by_approved_spender() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+        approve_token(&contract, &mut deps, env.clone(), None);
+
+        contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(SPENDER),
+                ExecuteMsg::Burn {
+                    token_id: TOKEN_ID.to_string(),
+                },
+            )
+            .unwrap();
+
+        assert!(!contract.tokens.has(&deps.storage, TOKEN_ID));
+    }
+
+    #[test]
+    fn should_fail_instantiate_with_royalty_share_above_one() {
+        let mut deps = mock_dependencies();
+        let contract: Contract = Contract::get_contract();
+
+        let init_result = contract
+            .instantiate(
+                deps.as_mut(),
+                mock_env(),
+                get_mock_info(OWNER),
+                InstantiateMsg {
+                    royalty: Some(RoyaltyMsg {
+                        payment_address: MINTER.to_string(),
+                        share: Decimal::percent(101),
+                    }),
+                    ..get_default_instantiate_msg()
+                },
+            )
+            .unwrap_err();
+
+        assert!(matches!(init_result, ContractError::InvalidRoyaltyShare {}))
+    }
+
+    #[test]
+    fn should_fail_mint_with_royalty_share_above_one() {
+        let (mut deps, contract, env, _) = initialize_contract();
+
+        let mint_result = contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(MINTER),
+                ExecuteMsg::Mint {
+                    token: TokenMsg {
+                        owner: OWNER.to_string(),
+                        token_id: Some(TOKEN_ID.to_string()),
+                        token_uri: None,
+                        royalty: Some(RoyaltyMsg {
+                            payment_address: MINTER.to_string(),
+                            share: Decimal::percent(150),
+                        }),
+                        extension: None,
+                        soulbound: false,
+                    },
+                },
+            )
+            .unwrap_err();
+
+        assert!(matches!(mint_result, ContractError::InvalidRoyaltyShare {}))
+    }
+
+    #[test]
+    fn should_fail_transfer_ownership_when_called_not_by_owner() {
+        let (mut deps, contract, env, _) = initialize_contract();
+
+        let transfer_result =
+            propose_ownership(&contract, &mut deps, env, STRANGER, None).unwrap_err();
+
+        assert!(matches!(transfer_result, ContractError::Unauthorized {}))
+    }
+
+    #[test]
+    fn should_transfer_ownership_in_two_steps() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        propose_ownership(&contract, &mut deps, env.clone(), OWNER, None).unwrap();
+
+        let ownership = contract.get_ownership(deps.as_ref()).unwrap();
+        assert_eq!(ownership.owner.unwrap(), OWNER);
+        assert_eq!(ownership.pending_owner.unwrap(), SPENDER);
+
+        contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(SPENDER),
+                ExecuteMsg::UpdateOwnership(OwnershipAction::AcceptOwnership),
+            )
+            .unwrap();
+
+        let ownership = contract.get_ownership(deps.as_ref()).unwrap();
+        assert_eq!(ownership.owner.unwrap(), SPENDER);
+        assert_eq!(ownership.pending_owner, None);
+    }
+
+    #[test]
+    fn should_fail_accept_ownership_without_pending_owner() {
+        let (mut deps, contract, env, _) = initialize_contract();
+
+        let accept_result = contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(SPENDER),
+                ExecuteMsg::UpdateOwnership(OwnershipAction::AcceptOwnership),
+            )
+            .unwrap_err();
+
+        assert!(matches!(accept_result, ContractError::NoPendingOwner {}))
+    }
+
+    #[test]
+    fn should_fail_accept_ownership_when_called_not_by_pending_owner() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        propose_ownership(&contract, &mut deps, env.clone(), OWNER, None).unwrap();
+
+        let accept_result = contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(STRANGER),
+                ExecuteMsg::UpdateOwnership(OwnershipAction::AcceptOwnership),
+            )
+            .unwrap_err();
+
+        assert!(matches!(accept_result, ContractError::NotPendingOwner {}))
+    }
+
+    #[test]
+    fn should_fail_accept_ownership_when_transfer_expired() {
+        let (mut deps, contract, mut env, _) = initialize_contract();
+        propose_ownership(
+            &contract,
+            &mut deps,
+            env.clone(),
+            OWNER,
+            Some(Expiration::AtHeight(env.block.height + 1)),
+        )
+        .unwrap();
+
+        env.block.height += 1;
+        let accept_result = contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(SPENDER),
+                ExecuteMsg::UpdateOwnership(OwnershipAction::AcceptOwnership),
+            )
+            .unwrap_err();
+
+        assert!(matches!(accept_result, ContractError::TransferExpired {}))
+    }
+
+    #[test]
+    fn should_renounce_ownership() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        propose_ownership(&contract, &mut deps, env.clone(), OWNER, None).unwrap();
+
+        contract
+            .execute(
+                deps.as_mut(),
+                env.clone(),
+                get_mock_info(OWNER),
+                ExecuteMsg::UpdateOwnership(OwnershipAction::RenounceOwnership),
+            )
+            .unwrap();
+
+        let ownership = contract.get_ownership(deps.as_ref()).unwrap();
+        assert_eq!(ownership.owner, None);
+        assert_eq!(ownership.pending_owner, None);
+
+        let update_result = contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(OWNER),
+                ExecuteMsg::UpdateMinter {
+                    minter: STRANGER.to_string(),
+                },
+            )
+            .unwrap_err();
+        assert!(matches!(update_result, ContractError::Unauthorized {}))
+    }
+
+    #[test]
+    fn should_fail_update_minter_when_called_not_by_owner() {
+        let (mut deps, contract, env, _) = initialize_contract();
+
+        let update_result = contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(MINTER),
+                ExecuteMsg::UpdateMinter {
+                    minter: STRANGER.to_string(),
+                },
+            )
+            .unwrap_err();
+
+        assert!(matches!(update_result, ContractError::Unauthorized {}))
+    }
+
+    #[test]
+    fn should_rotate_minter() {
+        let (mut deps, contract, env, _) = initialize_contract();
+
+        contract
+            .execute(
+                deps.as_mut(),
+                env.clone(),
+                get_mock_info(OWNER),
+                ExecuteMsg::UpdateMinter {
+                    minter: STRANGER.to_string(),
+                },
+            )
+            .unwrap();
+
+        let mint_result =
+            mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap_err();
+        assert!(matches!(mint_result, ContractError::Unauthorized {}));
+
+        mint_token(&contract, &mut deps, env.clone(), STRANGER, TOKEN_ID).unwrap();
+
+        let freeze_msg = ExecuteMsg::FreezeToken {
+            token_id: TOKEN_ID.to_string(),
+        };
+        let freeze_result = contract
+            .execute(
+                deps.as_mut(),
+                env.clone(),
+                get_mock_info(MINTER),
+                freeze_msg.clone(),
+            )
+            .unwrap_err();
+        assert!(matches!(freeze_result, ContractError::Unauthorized {}));
+
+        contract
+            .execute(deps.as_mut(), env, get_mock_info(STRANGER), freeze_msg)
+            .unwrap();
+    }
+
+    #[test]
+    fn should_hand_over_owner_roles_on_accept_ownership() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        propose_ownership(&contract, &mut deps, env.clone(), OWNER, None).unwrap();
+        contract
+            .execute(
+                deps.as_mut(),
+                env.clone(),
+                get_mock_info(SPENDER),
+                ExecuteMsg::UpdateOwnership(OwnershipAction::AcceptOwnership),
+            )
+            .unwrap();
+
+        for role in Role::OWNER_ROLES {
+            assert!(!contract.has_role(&deps.storage, role, &Addr::unchecked(OWNER)));
+            assert!(contract.has_role(&deps.storage, role, &Addr::unchecked(SPENDER)));
+        }
+
+        let pause_result = pause(&contract, &mut deps, env.clone(), OWNER, None).unwrap_err();
+        assert!(matches!(pause_result, ContractError::Unauthorized {}));
+
+        pause(&contract, &mut deps, env, SPENDER, None).unwrap();
+    }
+
+    #[test]
+    fn should_drop_owner_roles_on_renounce_ownership() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        contract
+            .execute(
+                deps.as_mut(),
+                env.clone(),
+                get_mock_info(OWNER),
+                ExecuteMsg::UpdateOwnership(OwnershipAction::RenounceOwnership),
+            )
+            .unwrap();
+
+        for role in Role::OWNER_ROLES {
+            assert!(!contract.has_role(&deps.storage, role, &Addr::unchecked(OWNER)));
+        }
+
+        let pause_result = pause(&contract, &mut deps, env, OWNER, None).unwrap_err();
+        assert!(matches!(pause_result, ContractError::Unauthorized {}));
+    }
+
+    fn update_role(
+        contract: &Contract,
+        deps: &mut OwnedDeps<MemoryStorage, MockApi, MockQuerier>,
+        caller: &str,
+        msg: ExecuteMsg,
+    ) -> Result<Response, ContractError> {
+        contract.execute(deps.as_mut(), mock_env(), get_mock_info(caller), msg)
+    }
+
+    #[test]
+    fn should_mint_with_granted_minter_role() {
+        let (mut deps, contract, env, _) = initialize_contract();
+
+        let grant_res = update_role(
+            &contract,
+            &mut deps,
+            OWNER,
+            ExecuteMsg::GrantRole {
+                role: Role::Minter,
+                address: STRANGER.to_string(),
+            },
+        )
+        .unwrap();
+
+        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+        mint_token(&contract, &mut deps, env, STRANGER, "2").unwrap();
+        insta::assert_json_snapshot!(grant_res);
+    }
+
+    #[test]
+    fn should_fail_grant_role_when_called_not_by_admin() {
+        let (mut deps, contract, _, _) = initialize_contract();
+
+        let grant_res = update_role(
+            &contract,
+            &mut deps,
+            MINTER,
+            ExecuteMsg::GrantRole {
+                role: Role::Minter,
+                address: STRANGER.to_string(),
+            },
+        )
+        .unwrap_err();
+
+        assert!(matches!(grant_res, ContractError::Unauthorized {}));
+    }
+
+    #[test]
+    fn should_revoke_role() {
+        let (mut deps, contract, env, _) = initialize_contract();
+
+        update_role(
+            &contract,
+            &mut deps,
+            OWNER,
+            ExecuteMsg::RevokeRole {
+                role: Role::Minter,
+                address: MINTER.to_string(),
+            },
+        )
+        .unwrap();
+
+        let mint_res = mint_token(&contract, &mut deps, env, MINTER, TOKEN_ID).unwrap_err();
+        assert!(matches!(mint_res, ContractError::Unauthorized {}));
+    }
+
+    #[test]
+    fn should_fail_revoke_admin_of_owner() {
+        let (mut deps, contract, _, _) = initialize_contract();
+        update_role(
+            &contract,
+            &mut deps,
+            OWNER,
+            ExecuteMsg::GrantRole {
+                role: Role::Admin,
+                address: STRANGER.to_string(),
+            },
+        )
+        .unwrap();
+
+        for caller in [OWNER, STRANGER] {
+            let revoke_res = update_role(
+                &contract,
+                &mut deps,
+                caller,
+                ExecuteMsg::RevokeRole {
+                    role: Role::Admin,
+                    address: OWNER.to_string(),
+                },
+            )
+            .unwrap_err();
+            assert!(matches!(
+                revoke_res,
+                ContractError::CannotRevokeOwnerAdmin {}
+            ));
+        }
+    }
+
+    #[test]
+    fn should_fail_revoke_last_admin() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        update_role(
+            &contract,
+            &mut deps,
+            OWNER,
+            ExecuteMsg::GrantRole {
+                role: Role::Admin,
+                address: STRANGER.to_string(),
+            },
+        )
+        .unwrap();
+        contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(OWNER),
+                ExecuteMsg::UpdateOwnership(OwnershipAction::RenounceOwnership),
+            )
+            .unwrap();
+
+        let revoke_res = update_role(
+            &contract,
+            &mut deps,
+            STRANGER,
+            ExecuteMsg::RevokeRole {
+                role: Role::Admin,
+                address: STRANGER.to_string(),
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(
+            revoke_res,
+            ContractError::CannotRevokeLastAdmin {}
+        ));
+    }
+
+    #[test]
+    fn should_require_metadata_editor_role_to_update_metadata() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+        update_role(
+            &contract,
+            &mut deps,
+            OWNER,
+            ExecuteMsg::GrantRole {
+                role: Role::Minter,
+                address: STRANGER.to_string(),
+            },
+        )
+        .unwrap();
+
+        let minter_res = update_token_uri(&contract, &mut deps, env.clone(), STRANGER).unwrap_err();
+        assert!(matches!(minter_res, ContractError::Unauthorized {}));
+
+        update_role(
+            &contract,
+            &mut deps,
+            OWNER,
+            ExecuteMsg::GrantRole {
+                role: Role::MetadataEditor,
+                address: STRANGER.to_string(),
+            },
+        )
+        .unwrap();
+        update_token_uri(&contract, &mut deps, env, STRANGER).unwrap();
+    }
+
+    #[test]
+    fn should_pause_with_granted_pauser_role() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        update_role(
+            &contract,
+            &mut deps,
+            OWNER,
+            ExecuteMsg::GrantRole {
+                role: Role::Pauser,
+                address: STRANGER.to_string(),
+            },
+        )
+        .unwrap();
+
+        pause(&contract, &mut deps, env, STRANGER, None).unwrap();
+
+        assert_eq!(
+            contract.paused.load(&deps.storage).unwrap(),
+            PauseScope::All
+        );
+    }
+
+    #[test]
+    fn should_move_admin_role_with_ownership() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        propose_ownership(&contract, &mut deps, env.clone(), OWNER, None).unwrap();
+
+        contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(SPENDER),
+                ExecuteMsg::UpdateOwnership(OwnershipAction::AcceptOwnership),
+            )
+            .unwrap();
+
+        let grant_msg = || ExecuteMsg::GrantRole {
+            role: Role::Minter,
+            address: STRANGER.to_string(),
+        };
+        let previous_owner_res = update_role(&contract, &mut deps, OWNER, grant_msg()).unwrap_err();
+        assert!(matches!(previous_owner_res, ContractError::Unauthorized {}));
+        update_role(&contract, &mut deps, SPENDER, grant_msg()).unwrap();
+    }
+
+    fn approve_token(
+        contract: &Contract,
+        deps: &mut OwnedDeps<MemoryStorage, MockApi, MockQuerier>,
+        env: Env,
+        expires: Option<Expiration>,
+    ) {
+        contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(OWNER),
+                ExecuteMsg::Approve {
+                    spender: SPENDER.to_string(),
+                    token_id: TOKEN_ID.to_string(),
+                    expires,
+                },
+            )
+            .unwrap();
+    }
+
+    fn approve_all(
+        contract: &Contract,
+        deps: &mut OwnedDeps<MemoryStorage, MockApi, MockQuerier>,
+        env: Env,
+        expires: Option<Expiration>,
+    ) {
+        contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(OWNER),
+                ExecuteMsg::ApproveAll {
+                    operator: SPENDER.to_string(),
+                    expires,
+                },
+            )
+            .unwrap();
+    }
+
+    fn propose_ownership(
+        contract: &Contract,
+        deps: &mut OwnedDeps<MemoryStorage, MockApi, MockQuerier>,
+        env: Env,
+        sender: &str,
+        expiry: Option<Expiration>,
+    ) -> Result<Response, ContractError> {
+        contract.execute(
+            deps.as_mut(),
+            env,
+            get_mock_info(sender),
+            ExecuteMsg::UpdateOwnership(OwnershipAction::TransferOwnership {
+                new_owner: SPENDER.to_string(),
+                expiry,
+            }),
+        )
     }
 }
@@ -1,16 +1,19 @@
 #[cfg(test)]
 mod tests {
-    use cosmwasm_std::{to_binary, Addr, Empty};
+    use cosmwasm_std::{to_binary, Addr, Binary, Empty, Event};
     use cw_multi_test::{App, Contract, ContractWrapper, Executor};
 
     use crate::{
         contract::{execute, instantiate, query},
         msg::{ExecuteMsg, InstantiateMsg, QueryMsg, TokenMsg},
+        receiver::example::{self, Deposit, VaultMsg},
         response::{ContractInfoResponse, NumTokensResponse, OwnerOfResponse},
-        state::CONTRACT_NAME,
-        utils::test_utils::{MINTER, OWNER, SYMBOL, TOKEN_ID},
+        state::{Extension, CONTRACT_NAME},
+        utils::test_utils::{get_default_instantiate_msg, MINTER, OWNER, SYMBOL, TOKEN_ID},
     };
 
+    const SPENDER: &str = "spender";
+
     #[test]
     fn contract_test() {
         let mut router = App::default();
@@ -29,14 +32,17 @@ mod tests {
             contract_info,
             ContractInfoResponse {
                 name: CONTRACT_NAME.to_string(),
-                symbol: SYMBOL.to_string()
+                symbol: SYMBOL.to_string(),
             }
         );
 
-        let token = TokenMsg {
+        let token: TokenMsg = TokenMsg {
             owner: owner.to_string(),
-            token_id: TOKEN_ID.to_string(),
+            token_id: Some(TOKEN_ID.to_string()),
             token_uri: None,
+            royalty: None,
+            extension: None,
+            soulbound: false,
         };
 
         router
@@ -53,9 +59,9 @@ mod tests {
             .query_wasm_smart(&first_contract_addr, &QueryMsg::NumTokens {})
             .unwrap();
 
-        assert_eq!(tokens_num.number, 1);
+        assert_eq!(tokens_num.count, 1);
 
-        let send_msg = ExecuteMsg::SendNft {
+        let send_msg: ExecuteMsg = ExecuteMsg::SendNft {
             token_id: TOKEN_ID.to_string(),
             contract: second_contract_addr.to_string(),
             msg: to_binary("Hello from first").unwrap(),
@@ -73,6 +79,7 @@ mod tests {
                 first_contract_addr,
                 &QueryMsg::OwnerOf {
                     token_id: TOKEN_ID.to_string(),
+                    include_expired: None,
                 },
             )
             .unwrap();
@@ -80,6 +87,293 @@ mod tests {
         assert_eq!(token_owner.owner, second_contract_addr);
     }
 
+    #[test]
+    fn vault_should_record_deposit_from_allowed_collection() {
+        let mut router = App::default();
+        let owner = Addr::unchecked(OWNER);
+
+        let collection_addr = get_contract(&mut router, owner.clone());
+        let vault_addr = get_vault(&mut router, owner.clone(), &[&collection_addr]);
+        mint_to(&mut router, &collection_addr, &owner);
+
+        let send_res = router
+            .execute_contract(
+                owner.clone(),
+                collection_addr.clone(),
+                &ExecuteMsg::<Extension>::SendNft {
+                    token_id: TOKEN_ID.to_string(),
+                    contract: vault_addr.to_string(),
+                    msg: to_binary(&VaultMsg::Deposit {
+                        memo: Some("for safekeeping".to_string()),
+                    })
+                    .unwrap(),
+                },
+                &[],
+            )
+            .unwrap();
+
+        assert!(send_res.has_event(
+            &Event::new("wasm-deposit")
+                .add_attribute("sender", OWNER)
+                .add_attribute("collection", collection_addr.as_str())
+                .add_attribute("token_id", TOKEN_ID)
+        ));
+
+        let deposit: Option<Deposit> = router
+            .wrap()
+            .query_wasm_smart(
+                &vault_addr,
+                &example::QueryMsg::Deposit {
+                    collection: collection_addr.to_string(),
+                    token_id: TOKEN_ID.to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(
+            deposit,
+            Some(Deposit {
+                depositor: owner,
+                memo: Some("for safekeeping".to_string())
+            })
+        );
+        assert_eq!(get_owner(&router, &collection_addr), vault_addr);
+    }
+
+    #[test]
+    fn vault_should_reject_other_collections() {
+        let mut router = App::default();
+        let owner = Addr::unchecked(OWNER);
+
+        let allowed_addr = get_contract(&mut router, owner.clone());
+        let other_addr = get_contract(&mut router, owner.clone());
+        let vault_addr = get_vault(&mut router, owner.clone(), &[&allowed_addr]);
+        mint_to(&mut router, &other_addr, &owner);
+
+        let err = router
+            .execute_contract(
+                owner.clone(),
+                other_addr.clone(),
+                &ExecuteMsg::<Extension>::SendNft {
+                    token_id: TOKEN_ID.to_string(),
+                    contract: vault_addr.to_string(),
+                    msg: to_binary(&VaultMsg::Deposit { memo: None }).unwrap(),
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            err.root_cause().to_string(),
+            format!("Collection not allowed: {other_addr}")
+        );
+        assert_eq!(get_owner(&router, &other_addr), owner);
+    }
+
+    #[test]
+    fn vault_should_reject_unknown_inner_message() {
+        let mut router = App::default();
+        let owner = Addr::unchecked(OWNER);
+
+        let collection_addr = get_contract(&mut router, owner.clone());
+        let vault_addr = get_vault(&mut router, owner.clone(), &[&collection_addr]);
+        mint_to(&mut router, &collection_addr, &owner);
+
+        let err = router
+            .execute_contract(
+                owner.clone(),
+                collection_addr.clone(),
+                &ExecuteMsg::<Extension>::SendNft {
+                    token_id: TOKEN_ID.to_string(),
+                    contract: vault_addr.to_string(),
+                    msg: Binary::from(br#"{"withdraw":{}}"#),
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert!(err.root_cause().to_string().starts_with("Error parsing"));
+        assert_eq!(get_owner(&router, &collection_addr), owner);
+    }
+
+    #[test]
+    fn spec_queries_should_deserialize_into_cw721_responses() {
+        let mut router = App::default();
+        let owner = Addr::unchecked(OWNER);
+
+        let collection_addr = get_contract(&mut router, owner.clone());
+        mint_to(&mut router, &collection_addr, &owner);
+        router
+            .execute_contract(
+                owner.clone(),
+                collection_addr.clone(),
+                &ExecuteMsg::<Extension>::Approve {
+                    spender: SPENDER.to_string(),
+                    token_id: TOKEN_ID.to_string(),
+                    expires: None,
+                },
+                &[],
+            )
+            .unwrap();
+        router
+            .execute_contract(
+                owner,
+                collection_addr.clone(),
+                &ExecuteMsg::<Extension>::ApproveAll {
+                    operator: SPENDER.to_string(),
+                    expires: None,
+                },
+                &[],
+            )
+            .unwrap();
+
+        let querier = router.wrap();
+
+        let contract_info: cw721::ContractInfoResponse = querier
+            .query_wasm_smart(&collection_addr, &QueryMsg::ContractInfo {})
+            .unwrap();
+        let num_tokens: cw721::NumTokensResponse = querier
+            .query_wasm_smart(&collection_addr, &QueryMsg::NumTokens {})
+            .unwrap();
+        let nft_info: cw721::NftInfoResponse<Extension> = querier
+            .query_wasm_smart(
+                &collection_addr,
+                &QueryMsg::NftInfo {
+                    token_id: TOKEN_ID.to_string(),
+                },
+            )
+            .unwrap();
+        let all_nft_info: cw721::AllNftInfoResponse<Extension> = querier
+            .query_wasm_smart(
+                &collection_addr,
+                &QueryMsg::AllNftInfo {
+                    token_id: TOKEN_ID.to_string(),
+                    include_expired: None,
+                },
+            )
+            .unwrap();
+        let owner_of: cw721::OwnerOfResponse = querier
+            .query_wasm_smart(
+                &collection_addr,
+                &QueryMsg::OwnerOf {
+                    token_id: TOKEN_ID.to_string(),
+                    include_expired: None,
+                },
+            )
+            .unwrap();
+        let approval: cw721::ApprovalResponse = querier
+            .query_wasm_smart(
+                &collection_addr,
+                &QueryMsg::Approval {
+                    token_id: TOKEN_ID.to_string(),
+                    spender: SPENDER.to_string(),
+                    include_expired: None,
+                },
+            )
+            .unwrap();
+        let approvals: cw721::ApprovalsResponse = querier
+            .query_wasm_smart(
+                &collection_addr,
+                &QueryMsg::Approvals {
+                    token_id: TOKEN_ID.to_string(),
+                    include_expired: None,
+                },
+            )
+            .unwrap();
+        let operators: cw721::OperatorsResponse = querier
+            .query_wasm_smart(
+                &collection_addr,
+                &QueryMsg::AllOperators {
+                    owner: OWNER.to_string(),
+                    include_expired: None,
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        let tokens: cw721::TokensResponse = querier
+            .query_wasm_smart(
+                &collection_addr,
+                &QueryMsg::Tokens {
+                    owner: OWNER.to_string(),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        let all_tokens: cw721::TokensResponse = querier
+            .query_wasm_smart(
+                &collection_addr,
+                &QueryMsg::AllTokens {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+
+        assert_eq!(contract_info.name, CONTRACT_NAME);
+        assert_eq!(num_tokens.count, 1);
+        assert_eq!(nft_info.token_uri, None);
+        assert_eq!(all_nft_info.access.owner, OWNER);
+        assert_eq!(owner_of.approvals.len(), 1);
+        assert_eq!(approval.approval.spender, SPENDER);
+        assert_eq!(approvals.approvals.len(), 1);
+        assert_eq!(operators.operators.len(), 1);
+        assert_eq!(tokens.tokens, vec![TOKEN_ID]);
+        assert_eq!(all_tokens.tokens, vec![TOKEN_ID]);
+    }
+
+    fn mint_to(router: &mut App, collection: &Addr, owner: &Addr) {
+        let token: TokenMsg = TokenMsg {
+            owner: owner.to_string(),
+            token_id: Some(TOKEN_ID.to_string()),
+            token_uri: None,
+            royalty: None,
+            extension: None,
+            soulbound: false,
+        };
+
+        router
+            .execute_contract(
+                Addr::unchecked(MINTER),
+                collection.clone(),
+                &ExecuteMsg::Mint { token },
+                &[],
+            )
+            .unwrap();
+    }
+
+    fn get_owner(router: &App, collection: &Addr) -> Addr {
+        let res: OwnerOfResponse = router
+            .wrap()
+            .query_wasm_smart(
+                collection,
+                &QueryMsg::OwnerOf {
+                    token_id: TOKEN_ID.to_string(),
+                    include_expired: None,
+                },
+            )
+            .unwrap();
+
+        res.owner
+    }
+
+    fn get_vault(router: &mut App, owner: Addr, collections: &[&Addr]) -> Addr {
+        let code_id = router.store_code(Box::new(ContractWrapper::new(
+            example::execute,
+            example::instantiate,
+            example::query,
+        )));
+
+        let init_msg = example::InstantiateMsg {
+            collections: collections.iter().map(|addr| addr.to_string()).collect(),
+        };
+
+        router
+            .instantiate_contract(code_id, owner, &init_msg, &[], "vault", None)
+            .unwrap()
+    }
+
     fn get_contract_code() -> Box<dyn Contract<Empty>> {
         let contract = ContractWrapper::new(execute, instantiate, query);
 
@@ -88,9 +382,8 @@ mod tests {
 
     fn get_contract(router: &mut App, owner: Addr) -> Addr {
         let init_msg = InstantiateMsg {
-            minter: MINTER.to_string(),
             name: CONTRACT_NAME.to_string(),
-            symbol: SYMBOL.to_string(),
+            ..get_default_instantiate_msg()
         };
 
         let code_id = router.store_code(get_contract_code());
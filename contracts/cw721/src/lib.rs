@@ -2,11 +2,13 @@ pub mod contract;
 mod error;
 pub mod execute;
 pub mod integration_tests;
+pub mod merkle;
+pub mod migrate;
 pub mod msg;
 pub mod query;
+pub mod receiver;
 pub mod response;
 pub mod state;
 pub mod utils;
 
 pub use crate::error::ContractError;
-
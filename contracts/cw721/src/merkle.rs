@@ -0,0 +1,175 @@
+//! Merkle allowlist of `(address, allocation)` pairs, hashed with sha256.
+//!
+//! A leaf is `sha256(address || allocation)`, the allocation being 4 big-endian bytes.
+//! A parent hashes its children in ascending order, so proofs carry no left/right flags,
+//! and the last node of a level with an odd number of nodes moves up unchanged.
+
+use sha2::{Digest, Sha256};
+
+pub type Hash = [u8; 32];
+
+pub fn leaf_hash(address: &str, allocation: u32) -> Hash {
+    Sha256::new()
+        .chain_update(address.as_bytes())
+        .chain_update(allocation.to_be_bytes())
+        .finalize()
+        .into()
+}
+
+fn parent_hash(first: &Hash, second: &Hash) -> Hash {
+    let (left, right) = if first <= second {
+        (first, second)
+    } else {
+        (second, first)
+    };
+
+    Sha256::new()
+        .chain_update(left)
+        .chain_update(right)
+        .finalize()
+        .into()
+}
+
+/// Recomputes the root from `leaf` and its proof, every proof element has to be 32 bytes
+pub fn verify_proof(root: &[u8], leaf: Hash, proof: &[impl AsRef<[u8]>]) -> bool {
+    let mut hash = leaf;
+    for sibling in proof {
+        let sibling: Hash = match sibling.as_ref().try_into() {
+            Ok(sibling) => sibling,
+            Err(_) => return false,
+        };
+        hash = parent_hash(&hash, &sibling);
+    }
+
+    root == hash
+}
+
+/// Builds the root and the proofs of an allowlist, for off-chain tooling and tests
+pub struct MerkleTree {
+    /// Leaves first, the root level last
+    levels: Vec<Vec<Hash>>,
+}
+
+impl MerkleTree {
+    pub fn new(leaves: Vec<Hash>) -> Self {
+        let mut levels = vec![leaves];
+        while levels[levels.len() - 1].len() > 1 {
+            let level = &levels[levels.len() - 1];
+            let parents = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [first, second] => parent_hash(first, second),
+                    [last] => *last,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(parents);
+        }
+
+        Self { levels }
+    }
+
+    pub fn from_allowlist<'s>(allowlist: impl IntoIterator<Item = (&'s str, u32)>) -> Self {
+        Self::new(
+            allowlist
+                .into_iter()
+                .map(|(address, allocation)| leaf_hash(address, allocation))
+                .collect(),
+        )
+    }
+
+    /// `None` for an empty allowlist
+    pub fn root(&self) -> Option<Hash> {
+        self.levels[self.levels.len() - 1].first().copied()
+    }
+
+    /// Siblings of the leaf at `index`, from the bottom of the tree up
+    pub fn proof(&self, mut index: usize) -> Option<Vec<Hash>> {
+        if index >= self.levels[0].len() {
+            return None;
+        }
+
+        let mut proof = vec![];
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+
+        Some(proof)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::HexBinary;
+
+    use super::{leaf_hash, verify_proof, Hash, MerkleTree};
+
+    const ALLOWLIST: [(&str, u32); 3] = [("alice", 1), ("bob", 2), ("carol", 3)];
+
+    fn hash(hex: &str) -> Hash {
+        HexBinary::from_hex(hex)
+            .unwrap()
+            .as_slice()
+            .try_into()
+            .unwrap()
+    }
+
+    #[test]
+    fn should_match_test_vectors() {
+        let tree = MerkleTree::from_allowlist(ALLOWLIST);
+
+        assert_eq!(
+            leaf_hash("alice", 1),
+            hash("ada16cd01b9ccc13ecd4cc9d25f6e24f42c03c6962b970f4867889a9d3dc048a")
+        );
+        assert_eq!(
+            tree.root().unwrap(),
+            hash("4acb415919e1e947ae2e0052e77b4665e43271eec992ed7e87f6870632773fc5")
+        );
+        assert_eq!(
+            tree.proof(1).unwrap(),
+            vec![
+                hash("ada16cd01b9ccc13ecd4cc9d25f6e24f42c03c6962b970f4867889a9d3dc048a"),
+                hash("035cd275150b615fda5084b1eab9f8713f023755a47cf8b7eefe955fce803da7"),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_verify_proof_of_every_leaf() {
+        let tree = MerkleTree::from_allowlist(ALLOWLIST);
+        let root = tree.root().unwrap();
+
+        for (index, (address, allocation)) in ALLOWLIST.into_iter().enumerate() {
+            let proof = tree.proof(index).unwrap();
+
+            assert!(verify_proof(&root, leaf_hash(address, allocation), &proof));
+            assert!(!verify_proof(
+                &root,
+                leaf_hash(address, allocation + 1),
+                &proof
+            ));
+        }
+    }
+
+    #[test]
+    fn should_use_leaf_as_root_of_single_leaf_tree() {
+        let tree = MerkleTree::from_allowlist([("alice", 1)]);
+
+        assert_eq!(tree.root().unwrap(), leaf_hash("alice", 1));
+        assert!(tree.proof(0).unwrap().is_empty());
+        assert!(MerkleTree::new(vec![]).root().is_none());
+    }
+
+    #[test]
+    fn should_reject_malformed_proof() {
+        let tree = MerkleTree::from_allowlist(ALLOWLIST);
+        let root = tree.root().unwrap();
+
+        assert!(!verify_proof(&root, leaf_hash("bob", 2), &[vec![0u8; 31]]));
+        assert!(tree.proof(ALLOWLIST.len()).is_none());
+    }
+}
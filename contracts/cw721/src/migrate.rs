@@ -0,0 +1,249 @@
+use cosmwasm_std::{DepsMut, Env, Order, Response, StdError};
+use cw2::{get_contract_version, set_contract_version, ContractVersion};
+use cw_events::ActionEvent;
+use cw_storage_plus::Item;
+use semver::Version;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    msg::MigrateMsg,
+    state::{
+        CollectionInfo, Contract, Role, CONTRACT_NAME, CONTRACT_VERSION, LEGACY_CONTRACT_INFO_KEY,
+    },
+    ContractError,
+};
+
+const LEGACY_CONTRACT_VERSION: &str = "0.1.0";
+
+impl<'a, T> Contract<'a, T>
+where
+    T: Serialize + DeserializeOwned + Clone,
+{
+    pub fn migrate(
+        &self,
+        mut deps: DepsMut,
+        _env: Env,
+        _msg: MigrateMsg,
+    ) -> Result<Response, ContractError> {
+        let stored = match get_contract_version(deps.storage) {
+            Ok(stored) => stored,
+            Err(err) => self.migrate_legacy_contract_info(deps.branch(), err)?,
+        };
+
+        if stored.contract != CONTRACT_NAME {
+            return Err(ContractError::WrongContract {
+                expected: CONTRACT_NAME.to_string(),
+                found: stored.contract,
+            });
+        }
+
+        let previous = parse_version(&stored.version)?;
+        let current = parse_version(CONTRACT_VERSION)?;
+
+        if previous > current {
+            return Err(ContractError::CannotDowngrade {
+                previous: stored.version,
+                current: CONTRACT_VERSION.to_string(),
+            });
+        }
+
+        self.migrate_state(deps.branch(), &previous)?;
+        set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+        Ok(ActionEvent::new("migrate")
+            .add_attribute("from_version", stored.version)
+            .add_attribute("to_version", CONTRACT_VERSION)
+            .into_response())
+    }
+
+    /// Brings storage written by `previous` up to the current layout.
+    /// Every layout change gets its own step, so that a contract can be
+    /// upgraded across several releases at once.
+    pub fn migrate_state(&self, deps: DepsMut, previous: &Version) -> Result<(), ContractError> {
+        // 0.2.0 counts the total supply and replaced the owner and minter checks with roles
+        if previous < &Version::new(0, 2, 0) {
+            let count = self
+                .tokens
+                .keys(deps.storage, None, None, Order::Ascending)
+                .count();
+            self.token_count.save(deps.storage, &(count as u64))?;
+
+            // the owner and the minter keep their permissions
+            if let Some(owner) = self.owner.may_load(deps.storage)? {
+                for role in Role::OWNER_ROLES {
+                    self.save_role(deps.storage, role, &owner)?;
+                }
+            }
+            let minter = self.minter.load(deps.storage)?;
+            for role in Role::MINTER_ROLES {
+                self.save_role(deps.storage, role, &minter)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Early deployments saved the collection info under the cw2 version key,
+    /// overwriting the version. Moves the info to its own key and reports the
+    /// deployment as the first release.
+    fn migrate_legacy_contract_info(
+        &self,
+        deps: DepsMut,
+        version_err: StdError,
+    ) -> Result<ContractVersion, ContractError> {
+        let legacy_info: Item<CollectionInfo> = Item::new(LEGACY_CONTRACT_INFO_KEY);
+        let contract_info = legacy_info.load(deps.storage).map_err(|_| version_err)?;
+
+        self.contract_info.save(deps.storage, &contract_info)?;
+
+        Ok(ContractVersion {
+            contract: CONTRACT_NAME.to_string(),
+            version: LEGACY_CONTRACT_VERSION.to_string(),
+        })
+    }
+}
+
+fn parse_version(version: &str) -> Result<Version, ContractError> {
+    Version::parse(version).map_err(|err| StdError::generic_err(err.to_string()).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::Addr;
+    use cw2::{get_contract_version, set_contract_version};
+    use cw_storage_plus::{Item, Map};
+    use serde::{Deserialize, Serialize};
+
+    use crate::{
+        msg::MigrateMsg,
+        state::{Role, CONTRACT_NAME, CONTRACT_VERSION, LEGACY_CONTRACT_INFO_KEY, TOKENS_PK},
+        utils::test_utils::{initialize_contract, mint_token, MINTER, OWNER, TOKEN_ID},
+        ContractError,
+    };
+
+    /// Token as stored before approvals, royalties and extensions existed
+    #[derive(Serialize, Deserialize)]
+    struct LegacyToken {
+        owner: Addr,
+        token_id: String,
+        token_uri: Option<String>,
+    }
+
+    /// Collection info as stored under the cw2 version key by the first release
+    #[derive(Serialize, Deserialize)]
+    struct LegacyContractInfo {
+        name: String,
+        symbol: String,
+    }
+
+    #[test]
+    fn should_fail_migrate_from_another_contract() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        set_contract_version(&mut deps.storage, "crates.io:another", CONTRACT_VERSION).unwrap();
+
+        let migrate_result = contract
+            .migrate(deps.as_mut(), env, MigrateMsg {})
+            .unwrap_err();
+
+        assert!(matches!(
+            migrate_result,
+            ContractError::WrongContract { .. }
+        ))
+    }
+
+    #[test]
+    fn should_fail_migrate_to_older_version() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        set_contract_version(&mut deps.storage, CONTRACT_NAME, "999.0.0").unwrap();
+
+        let migrate_result = contract
+            .migrate(deps.as_mut(), env, MigrateMsg {})
+            .unwrap_err();
+
+        assert!(matches!(
+            migrate_result,
+            ContractError::CannotDowngrade { .. }
+        ))
+    }
+
+    #[test]
+    fn should_migrate_and_keep_tokens() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        set_contract_version(&mut deps.storage, CONTRACT_NAME, "0.0.1").unwrap();
+
+        let legacy_token = LegacyToken {
+            owner: Addr::unchecked(OWNER),
+            token_id: TOKEN_ID.to_string(),
+            token_uri: None,
+        };
+        Map::new(TOKENS_PK)
+            .save(&mut deps.storage, TOKEN_ID, &legacy_token)
+            .unwrap();
+
+        contract.migrate(deps.as_mut(), env, MigrateMsg {}).unwrap();
+
+        let version = get_contract_version(&deps.storage).unwrap();
+        assert_eq!(version.version, CONTRACT_VERSION);
+
+        let token = contract.tokens.load(&deps.storage, TOKEN_ID).unwrap();
+        assert_eq!(token.owner, OWNER);
+        assert!(token.approvals.is_empty());
+        assert_eq!(token.extension, None);
+    }
+
+    #[test]
+    fn should_migrate_contract_info_stored_under_version_key() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        let contract_info = contract.contract_info.load(&deps.storage).unwrap();
+        contract.contract_info.remove(&mut deps.storage);
+        Item::new(LEGACY_CONTRACT_INFO_KEY)
+            .save(
+                &mut deps.storage,
+                &LegacyContractInfo {
+                    name: contract_info.name.clone(),
+                    symbol: contract_info.symbol.clone(),
+                },
+            )
+            .unwrap();
+
+        contract.migrate(deps.as_mut(), env, MigrateMsg {}).unwrap();
+
+        let version = get_contract_version(&deps.storage).unwrap();
+        assert_eq!(version.contract, CONTRACT_NAME);
+        assert_eq!(version.version, CONTRACT_VERSION);
+        assert_eq!(
+            contract.contract_info.load(&deps.storage).unwrap(),
+            contract_info
+        );
+    }
+
+    #[test]
+    fn should_backfill_token_count() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, "2").unwrap();
+        contract.token_count.remove(&mut deps.storage);
+        set_contract_version(&mut deps.storage, CONTRACT_NAME, "0.1.0").unwrap();
+
+        contract.migrate(deps.as_mut(), env, MigrateMsg {}).unwrap();
+
+        assert_eq!(contract.token_count(&deps.storage).unwrap(), 2);
+    }
+
+    #[test]
+    fn should_grant_roles_of_owner_and_minter() {
+        let (mut deps, contract, env, _) = initialize_contract();
+        contract.roles.clear(&mut deps.storage);
+        set_contract_version(&mut deps.storage, CONTRACT_NAME, "0.1.0").unwrap();
+
+        contract.migrate(deps.as_mut(), env, MigrateMsg {}).unwrap();
+
+        let owner = Addr::unchecked(OWNER);
+        let minter = Addr::unchecked(MINTER);
+        assert!(contract.has_role(&deps.storage, Role::Admin, &owner));
+        assert!(contract.has_role(&deps.storage, Role::Pauser, &owner));
+        assert!(contract.has_role(&deps.storage, Role::Minter, &minter));
+        assert!(contract.has_role(&deps.storage, Role::MetadataEditor, &minter));
+        assert!(!contract.has_role(&deps.storage, Role::Minter, &owner));
+    }
+}
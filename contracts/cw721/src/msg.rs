@@ -1,21 +1,53 @@
 use crate::response::{
-    ContractInfoResponse, NftInfoResponse, NumTokensResponse, OwnerOfResponse, TokensResponse,
+    AllNftInfoResponse, AllowlistMintedResponse, ApprovalResponse, ApprovalsResponse,
+    BalanceResponse, CheckRoyaltiesResponse, CollectionConfigResponse, ContractInfoResponse,
+    CurrentPhaseResponse, HasRoleResponse, MerkleRootResponse, MinterResponse, NftInfoResponse,
+    NumTokensResponse, OperatorResponse, OperatorsResponse, OwnerOfResponse, OwnershipResponse,
+    PausedResponse, RoleMembersResponse, RoyaltiesInfoResponse, SaleResponse, TokenFlagsResponse,
+    TokenHistoryResponse, TokensInfoResponse, TokensResponse,
 };
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Binary;
+use cosmwasm_std::{
+    to_binary, Binary, Coin, CosmosMsg, Decimal, HexBinary, StdResult, Uint128, WasmMsg,
+};
 use cw721::Cw721ReceiveMsg;
+use cw_utils::Expiration;
+
+use crate::state::{Extension, MintPhase, PauseScope, Role, SaleConfig, TokenIdRules};
 
 #[cw_serde]
 pub struct InstantiateMsg {
     pub name: String,
     pub symbol: String,
     pub minter: String,
+    pub royalty: Option<RoyaltyMsg>,
+    /// Assigns sequential ids to minted tokens instead of taking them from `TokenMsg`
+    #[serde(default)]
+    pub auto_token_id: bool,
+    /// Upper bound for the number of tokens, can only be lowered later
+    pub max_supply: Option<u64>,
+    /// Makes every token of the collection non-transferable
+    #[serde(default)]
+    pub soulbound: bool,
+    /// Number of provenance records kept per token, 0 disables the history
+    pub provenance_limit: Option<u32>,
+    /// Format of the token ids given on mint, printable ids of up to 128 characters by default
+    pub token_id_rules: Option<TokenIdRules>,
+    /// Opens the public mint, which needs sequential token ids
+    pub sale: Option<SaleConfig>,
 }
 
 #[cw_serde]
-pub enum ExecuteMsg {
+pub struct MigrateMsg {}
+
+#[cw_serde]
+pub enum ExecuteMsg<T = Extension> {
     Mint {
-        token: TokenMsg,
+        token: TokenMsg<T>,
+    },
+    /// Mints all of the tokens or none of them
+    MintBatch {
+        tokens: Vec<TokenMsg<T>>,
     },
     TransferNft {
         token_id: String,
@@ -26,21 +58,210 @@ pub enum ExecuteMsg {
         contract: String,
         msg: Binary,
     },
+    /// Transfers all of the tokens or none of them
+    BatchTransferNft {
+        token_ids: Vec<String>,
+        to: String,
+    },
+    /// Sends one `ReceiveNft` per token, or a single `BatchReceiveNft`
+    /// if the receiver has enabled it with `SetBatchReceive`
+    BatchSendNft {
+        token_ids: Vec<String>,
+        contract: String,
+        msg: Binary,
+    },
+    /// Called by a receiver contract to opt in to (or out of) batched callbacks
+    SetBatchReceive {
+        enabled: bool,
+    },
     ReceiveNft(Cw721ReceiveMsg),
+    Approve {
+        spender: String,
+        token_id: String,
+        expires: Option<Expiration>,
+    },
+    Revoke {
+        spender: String,
+        token_id: String,
+    },
+    ApproveAll {
+        operator: String,
+        expires: Option<Expiration>,
+    },
+    RevokeAll {
+        operator: String,
+    },
+    Burn {
+        token_id: String,
+    },
+    UpdateTokenUri {
+        token_id: String,
+        token_uri: Option<String>,
+    },
+    /// Permanently locks the metadata of one token
+    FreezeToken {
+        token_id: String,
+    },
+    /// Permanently locks the metadata of the whole collection
+    FreezeMetadata {},
+    UpdateOwnership(OwnershipAction),
+    /// Replaces the minter reported by `Minter`, handing over its minter and metadata editor roles
+    UpdateMinter {
+        minter: String,
+    },
+    GrantRole {
+        role: Role,
+        address: String,
+    },
+    /// The owner keeps its admin role until ownership changes hands
+    RevokeRole {
+        role: Role,
+        address: String,
+    },
+    /// Lowers the supply cap, raising it is not allowed
+    UpdateMaxSupply {
+        max_supply: u64,
+    },
+    /// Replaces the terms of the public mint, `None` closes it
+    UpdateSale {
+        sale: Option<SaleConfig>,
+    },
+    /// Mints `quantity` tokens, one by default, to anyone who pays their exact price
+    PublicMint {
+        owner: String,
+        quantity: Option<u32>,
+    },
+    /// Replaces the root of the allowlist, `None` closes the allowlist mint
+    SetMerkleRoot {
+        root: Option<HexBinary>,
+    },
+    /// Mints `quantity` tokens, one by default, to an allowlisted sender within its allocation.
+    /// `proof` shows that `(sender, allocation)` is a leaf of the allowlist, see `merkle`
+    AllowlistMint {
+        proof: Vec<HexBinary>,
+        allocation: u32,
+        quantity: Option<u32>,
+    },
+    /// Replaces the mint schedule, which is locked once its first phase has started
+    UpdatePhases {
+        phases: Vec<MintPhase>,
+    },
+    /// Mints `quantity` tokens, one by default, to the sender during the current phase.
+    /// Allowlist phases need the `proof` of `(sender, allocation)`
+    PhaseMint {
+        quantity: Option<u32>,
+        proof: Option<Vec<HexBinary>>,
+        allocation: Option<u32>,
+    },
+    /// Sends the proceeds of the paid mints
+    Withdraw {
+        recipient: String,
+        amount: Coin,
+    },
+    /// Halts minting, transfers or everything, defaults to everything
+    Pause {
+        scope: Option<PauseScope>,
+    },
+    Unpause {},
 }
 
 #[cw_serde]
-pub struct TokenMsg {
+pub enum OwnershipAction {
+    /// Proposes a new owner, who has to accept before `expiry`
+    TransferOwnership {
+        new_owner: String,
+        expiry: Option<Expiration>,
+    },
+    AcceptOwnership,
+    /// Leaves the contract without an owner, which cannot be undone
+    RenounceOwnership,
+}
+
+/// Callback sent by `BatchSendNft` to receivers that opted in to batches
+#[cw_serde]
+pub struct Cw721BatchReceiveMsg {
+    pub sender: String,
+    pub token_ids: Vec<String>,
+    pub msg: Binary,
+}
+
+impl Cw721BatchReceiveMsg {
+    pub fn into_binary(self) -> StdResult<Binary> {
+        to_binary(&ReceiverExecuteMsg::BatchReceiveNft(self))
+    }
+
+    pub fn into_cosmos_msg<C: Into<String>>(self, contract_addr: C) -> StdResult<CosmosMsg> {
+        Ok(WasmMsg::Execute {
+            contract_addr: contract_addr.into(),
+            msg: self.into_binary()?,
+            funds: vec![],
+        }
+        .into())
+    }
+}
+
+#[cw_serde]
+enum ReceiverExecuteMsg {
+    BatchReceiveNft(Cw721BatchReceiveMsg),
+}
+
+#[cw_serde]
+pub struct TokenMsg<T = Extension> {
     pub owner: String,
-    pub token_id: String,
+    /// Must be empty when the collection assigns ids itself
+    pub token_id: Option<String>,
     pub token_uri: Option<String>,
+    /// Overrides the collection royalty for this token
+    pub royalty: Option<RoyaltyMsg>,
+    pub extension: T,
+    /// Makes this token non-transferable
+    #[serde(default)]
+    pub soulbound: bool,
+}
+
+#[cw_serde]
+pub struct RoyaltyMsg {
+    pub payment_address: String,
+    /// Fraction of the sale price, at most `Decimal::one()`
+    pub share: Decimal,
 }
 
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
     #[returns(OwnerOfResponse)]
-    OwnerOf { token_id: String },
+    OwnerOf {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+
+    #[returns(ApprovalResponse)]
+    Approval {
+        token_id: String,
+        spender: String,
+        include_expired: Option<bool>,
+    },
+
+    #[returns(ApprovalsResponse)]
+    Approvals {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+
+    #[returns(OperatorResponse)]
+    Operator {
+        owner: String,
+        operator: String,
+        include_expired: Option<bool>,
+    },
+
+    #[returns(OperatorsResponse)]
+    AllOperators {
+        owner: String,
+        include_expired: Option<bool>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 
     #[returns(NumTokensResponse)]
     NumTokens {},
@@ -48,19 +269,106 @@ pub enum QueryMsg {
     #[returns(ContractInfoResponse)]
     ContractInfo {},
 
-    #[returns(NftInfoResponse)]
+    #[returns(NftInfoResponse<Extension>)]
     NftInfo { token_id: String },
 
+    #[returns(AllNftInfoResponse<Extension>)]
+    AllNftInfo {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+
     #[returns(TokensResponse)]
     Tokens {
         owner: String,
         start_after: Option<String>,
-        limit: Option<u128>,
+        limit: Option<u32>,
     },
 
     #[returns(TokensResponse)]
     AllTokens {
         start_after: Option<String>,
-        limit: Option<u128>,
+        limit: Option<u32>,
+    },
+
+    #[returns(MinterResponse)]
+    Minter {},
+
+    #[returns(OwnershipResponse)]
+    Ownership {},
+
+    #[returns(PausedResponse)]
+    Paused {},
+
+    /// Settings chosen at instantiate, and whether the collection metadata is frozen
+    #[returns(CollectionConfigResponse)]
+    CollectionConfig {},
+
+    #[returns(TokenFlagsResponse)]
+    TokenFlags { token_id: String },
+
+    #[returns(SaleResponse)]
+    Sale {},
+
+    #[returns(MerkleRootResponse)]
+    MerkleRoot {},
+
+    #[returns(CurrentPhaseResponse)]
+    CurrentPhase {},
+
+    /// Tokens minted by `address` from the allowlist with `root`, the current Merkle root by default.
+    /// Allowlist phases with the same root share the count
+    #[returns(AllowlistMintedResponse)]
+    AllowlistMinted {
+        address: String,
+        root: Option<HexBinary>,
+    },
+
+    /// Funds held by the contract
+    #[returns(BalanceResponse)]
+    Balance {},
+
+    #[returns(HasRoleResponse)]
+    HasRole { role: Role, address: String },
+
+    #[returns(RoleMembersResponse)]
+    RoleMembers {
+        role: Role,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Chain of custody of the token, oldest records first
+    #[returns(TokenHistoryResponse)]
+    TokenHistory {
+        token_id: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    /// CW-2981: royalty owed to the creator for a sale of the token at `sale_price`
+    #[returns(RoyaltiesInfoResponse)]
+    RoyaltyInfo {
+        token_id: String,
+        sale_price: Uint128,
+    },
+
+    /// CW-2981: whether the contract implements royalties
+    #[returns(CheckRoyaltiesResponse)]
+    CheckRoyalties {},
+
+    /// Same as `Tokens`, but returns whole tokens instead of ids
+    #[returns(TokensInfoResponse<Extension>)]
+    TokensInfo {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Same as `AllTokens`, but returns whole tokens instead of ids
+    #[returns(TokensInfoResponse<Extension>)]
+    AllTokensInfo {
+        start_after: Option<String>,
+        limit: Option<u32>,
     },
 }
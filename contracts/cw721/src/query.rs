@@ -1,21 +1,73 @@
-use cosmwasm_std::{to_binary, Binary, Deps, Order, StdResult};
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Deps, Env, HexBinary, Order, StdError, StdResult, Uint128,
+};
 use cw_storage_plus::Bound;
+use cw_utils::Expiration;
+use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{
     msg::QueryMsg,
     response::{
-        ContractInfoResponse, NftInfoResponse, NumTokensResponse, OwnerOfResponse, TokensResponse,
+        AllNftInfoResponse, AllowlistMintedResponse, ApprovalResponse, ApprovalsResponse,
+        BalanceResponse, CheckRoyaltiesResponse, CollectionConfigResponse, ContractInfoResponse,
+        CurrentPhaseResponse, HasRoleResponse, MerkleRootResponse, MinterResponse, NftInfoResponse,
+        NumTokensResponse, OperatorResponse, OperatorsResponse, OwnerOfResponse, OwnershipResponse,
+        PausedResponse, RoleMembersResponse, RoyaltiesInfoResponse, SaleResponse,
+        TokenFlagsResponse, TokenHistoryResponse, TokensInfoResponse, TokensResponse,
     },
-    state::{Contract, Token},
+    state::{Approval, Contract, Provenance, Role, Token},
 };
 
-impl<'a> Contract<'a> {
-    pub fn query(&self, deps: Deps, msg: QueryMsg) -> StdResult<Binary> {
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 100;
+
+impl<'a, T> Contract<'a, T>
+where
+    T: Serialize + DeserializeOwned + Clone,
+{
+    pub fn query(&self, deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         match msg {
             QueryMsg::ContractInfo {} => to_binary(&self.get_contract_info(deps)?),
             QueryMsg::NftInfo { token_id } => to_binary(&self.get_nft_info(deps, token_id)?),
+            QueryMsg::AllNftInfo {
+                token_id,
+                include_expired,
+            } => to_binary(&self.get_all_nft_info(
+                deps,
+                env,
+                token_id,
+                include_expired.unwrap_or(false),
+            )?),
             QueryMsg::NumTokens {} => to_binary(&self.get_num_tokens(deps)?),
-            QueryMsg::OwnerOf { token_id } => to_binary(&self.get_owner_of_token(deps, token_id)?),
+            QueryMsg::OwnerOf {
+                token_id,
+                include_expired,
+            } => to_binary(&self.get_owner_of_token(
+                deps,
+                env,
+                token_id,
+                include_expired.unwrap_or(false),
+            )?),
+            QueryMsg::Approval {
+                token_id,
+                spender,
+                include_expired,
+            } => to_binary(&self.get_approval(
+                deps,
+                env,
+                token_id,
+                spender,
+                include_expired.unwrap_or(false),
+            )?),
+            QueryMsg::Approvals {
+                token_id,
+                include_expired,
+            } => to_binary(&self.get_approvals(
+                deps,
+                env,
+                token_id,
+                include_expired.unwrap_or(false),
+            )?),
             QueryMsg::Tokens {
                 owner,
                 start_after,
@@ -24,35 +76,343 @@ impl<'a> Contract<'a> {
             QueryMsg::AllTokens { start_after, limit } => {
                 to_binary(&self.get_all_tokens(deps, start_after, limit)?)
             }
+            QueryMsg::TokensInfo {
+                owner,
+                start_after,
+                limit,
+            } => to_binary(&self.get_owner_tokens_info(deps, owner, start_after, limit)?),
+            QueryMsg::AllTokensInfo { start_after, limit } => {
+                to_binary(&self.get_all_tokens_info(deps, start_after, limit)?)
+            }
+            QueryMsg::Minter {} => to_binary(&self.get_minter(deps)?),
+            QueryMsg::Ownership {} => to_binary(&self.get_ownership(deps)?),
+            QueryMsg::Paused {} => to_binary(&self.get_paused(deps)?),
+            QueryMsg::CollectionConfig {} => to_binary(&self.get_collection_config(deps)?),
+            QueryMsg::TokenFlags { token_id } => to_binary(&self.get_token_flags(deps, token_id)?),
+            QueryMsg::Sale {} => to_binary(&self.get_sale(deps)?),
+            QueryMsg::MerkleRoot {} => to_binary(&self.get_merkle_root(deps)?),
+            QueryMsg::CurrentPhase {} => to_binary(&self.get_current_phase(deps, env)?),
+            QueryMsg::AllowlistMinted { address, root } => {
+                to_binary(&self.get_allowlist_minted(deps, address, root)?)
+            }
+            QueryMsg::Balance {} => to_binary(&self.get_balance(deps, env)?),
+            QueryMsg::HasRole { role, address } => {
+                to_binary(&self.get_has_role(deps, role, address)?)
+            }
+            QueryMsg::RoleMembers {
+                role,
+                start_after,
+                limit,
+            } => to_binary(&self.get_role_members(deps, role, start_after, limit)?),
+            QueryMsg::TokenHistory {
+                token_id,
+                start_after,
+                limit,
+            } => to_binary(&self.get_token_history(deps, token_id, start_after, limit)?),
+            QueryMsg::RoyaltyInfo {
+                token_id,
+                sale_price,
+            } => to_binary(&self.get_royalty_info(deps, token_id, sale_price)?),
+            QueryMsg::CheckRoyalties {} => to_binary(&self.check_royalties()),
+            QueryMsg::Operator {
+                owner,
+                operator,
+                include_expired,
+            } => to_binary(&self.get_operator(
+                deps,
+                env,
+                owner,
+                operator,
+                include_expired.unwrap_or(false),
+            )?),
+            QueryMsg::AllOperators {
+                owner,
+                include_expired,
+                start_after,
+                limit,
+            } => to_binary(&self.get_all_operators(
+                deps,
+                env,
+                owner,
+                include_expired.unwrap_or(false),
+                start_after,
+                limit,
+            )?),
         }
     }
 }
 
-impl<'a> Contract<'a> {
+impl<'a, T> Contract<'a, T>
+where
+    T: Serialize + DeserializeOwned + Clone,
+{
     pub fn get_contract_info(&self, deps: Deps) -> StdResult<ContractInfoResponse> {
-        self.contract_info.load(deps.storage)
+        let info = self.contract_info.load(deps.storage)?;
+
+        Ok(ContractInfoResponse {
+            name: info.name,
+            symbol: info.symbol,
+        })
+    }
+
+    pub fn get_collection_config(&self, deps: Deps) -> StdResult<CollectionConfigResponse> {
+        let info = self.contract_info.load(deps.storage)?;
+
+        Ok(CollectionConfigResponse {
+            auto_token_id: info.auto_token_id,
+            max_supply: info.max_supply,
+            soulbound: info.soulbound,
+            metadata_frozen: self.metadata_frozen(deps.storage)?,
+        })
+    }
+
+    pub fn get_token_flags(&self, deps: Deps, token_id: String) -> StdResult<TokenFlagsResponse> {
+        self.tokens.load(deps.storage, &token_id)?;
+
+        Ok(TokenFlagsResponse {
+            frozen: self.frozen_tokens.has(deps.storage, &token_id),
+            soulbound: self.is_soulbound(deps.storage, &token_id)?,
+        })
+    }
+
+    pub fn get_minter(&self, deps: Deps) -> StdResult<MinterResponse> {
+        let minter = self.minter.load(deps.storage)?;
+
+        Ok(MinterResponse { minter })
+    }
+
+    pub fn get_token_history(
+        &self,
+        deps: Deps,
+        token_id: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<TokenHistoryResponse> {
+        let limit = page_limit(limit);
+        let start = start_after.map(Bound::exclusive);
+
+        let history: StdResult<Vec<Provenance>> = self
+            .provenance
+            .prefix(&token_id)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|record| record.map(|(_, record)| record))
+            .collect();
+
+        Ok(TokenHistoryResponse::new(history?, limit))
+    }
+
+    pub fn get_paused(&self, deps: Deps) -> StdResult<PausedResponse> {
+        Ok(PausedResponse {
+            scope: self.paused.may_load(deps.storage)?,
+        })
+    }
+
+    pub fn get_sale(&self, deps: Deps) -> StdResult<SaleResponse> {
+        Ok(SaleResponse {
+            sale: self.sale.may_load(deps.storage)?,
+        })
+    }
+
+    pub fn get_merkle_root(&self, deps: Deps) -> StdResult<MerkleRootResponse> {
+        Ok(MerkleRootResponse {
+            root: self.merkle_root.may_load(deps.storage)?,
+        })
     }
 
-    pub fn get_nft_info(&self, deps: Deps, token_id: String) -> StdResult<NftInfoResponse> {
+    pub fn get_current_phase(&self, deps: Deps, env: Env) -> StdResult<CurrentPhaseResponse> {
+        Ok(CurrentPhaseResponse {
+            phase: self
+                .current_phase(deps.storage, env.block.time)?
+                .map(|(_, phase)| phase),
+        })
+    }
+
+    pub fn get_allowlist_minted(
+        &self,
+        deps: Deps,
+        address: String,
+        root: Option<HexBinary>,
+    ) -> StdResult<AllowlistMintedResponse> {
+        let addr = deps.api.addr_validate(&address)?;
+        let root = match root {
+            Some(root) => Some(root),
+            None => self.merkle_root.may_load(deps.storage)?,
+        };
+        let minted = match root {
+            Some(root) => self
+                .allowlist_minted
+                .may_load(deps.storage, (&root, &addr))?
+                .unwrap_or_default(),
+            None => 0,
+        };
+
+        Ok(AllowlistMintedResponse { minted })
+    }
+
+    pub fn get_balance(&self, deps: Deps, env: Env) -> StdResult<BalanceResponse> {
+        Ok(BalanceResponse {
+            amount: deps.querier.query_all_balances(env.contract.address)?,
+        })
+    }
+
+    pub fn get_has_role(
+        &self,
+        deps: Deps,
+        role: Role,
+        address: String,
+    ) -> StdResult<HasRoleResponse> {
+        let addr = deps.api.addr_validate(&address)?;
+
+        Ok(HasRoleResponse {
+            has_role: self.has_role(deps.storage, role, &addr),
+        })
+    }
+
+    pub fn get_role_members(
+        &self,
+        deps: Deps,
+        role: Role,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<RoleMembersResponse> {
+        let limit = page_limit(limit);
+        let start_addr = start_after
+            .map(|addr| deps.api.addr_validate(&addr))
+            .transpose()?;
+        let start = start_addr.as_ref().map(Bound::exclusive);
+
+        let members: StdResult<Vec<Addr>> = self
+            .roles
+            .prefix(role.as_str())
+            .keys(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .collect();
+
+        Ok(RoleMembersResponse { members: members? })
+    }
+
+    pub fn get_ownership(&self, deps: Deps) -> StdResult<OwnershipResponse> {
+        let pending_owner = self.pending_owner.may_load(deps.storage)?;
+
+        Ok(OwnershipResponse {
+            owner: self.owner.may_load(deps.storage)?,
+            pending_owner: pending_owner.clone().map(|pending| pending.new_owner),
+            pending_expiry: pending_owner.map(|pending| pending.expiry),
+        })
+    }
+
+    pub fn get_royalty_info(
+        &self,
+        deps: Deps,
+        token_id: String,
+        sale_price: Uint128,
+    ) -> StdResult<RoyaltiesInfoResponse> {
+        let token = self.tokens.load(deps.storage, &token_id)?;
+        let royalty = match token.royalty {
+            Some(royalty) => Some(royalty),
+            None => self.default_royalty.may_load(deps.storage)?,
+        };
+
+        Ok(match royalty {
+            Some(royalty) => RoyaltiesInfoResponse {
+                address: royalty.payment_address.into_string(),
+                royalty_amount: sale_price * royalty.share,
+            },
+            None => RoyaltiesInfoResponse {
+                address: String::new(),
+                royalty_amount: Uint128::zero(),
+            },
+        })
+    }
+
+    pub fn check_royalties(&self) -> CheckRoyaltiesResponse {
+        CheckRoyaltiesResponse {
+            royalty_payments: true,
+        }
+    }
+
+    pub fn get_nft_info(&self, deps: Deps, token_id: String) -> StdResult<NftInfoResponse<T>> {
         let token = self.tokens.load(deps.storage, &token_id)?;
 
         Ok(NftInfoResponse {
             token_uri: token.token_uri,
+            extension: token.extension,
         })
     }
 
-    pub fn get_num_tokens(&self, deps: Deps) -> StdResult<NumTokensResponse> {
-        let tokens = self.tokens.keys(deps.storage, None, None, Order::Ascending);
+    pub fn get_all_nft_info(
+        &self,
+        deps: Deps,
+        env: Env,
+        token_id: String,
+        include_expired: bool,
+    ) -> StdResult<AllNftInfoResponse<T>> {
+        Ok(AllNftInfoResponse {
+            access: self.get_owner_of_token(deps, env, token_id.clone(), include_expired)?,
+            info: self.get_nft_info(deps, token_id)?,
+        })
+    }
 
+    pub fn get_num_tokens(&self, deps: Deps) -> StdResult<NumTokensResponse> {
         Ok(NumTokensResponse {
-            number: tokens.count() as u128,
+            count: self.token_count(deps.storage)?,
         })
     }
 
-    pub fn get_owner_of_token(&self, deps: Deps, token_id: String) -> StdResult<OwnerOfResponse> {
+    pub fn get_owner_of_token(
+        &self,
+        deps: Deps,
+        env: Env,
+        token_id: String,
+        include_expired: bool,
+    ) -> StdResult<OwnerOfResponse> {
         let token = self.tokens.load(deps.storage, &token_id)?;
 
-        Ok(OwnerOfResponse { owner: token.owner })
+        Ok(OwnerOfResponse {
+            owner: token.owner,
+            approvals: filter_approvals(&env, token.approvals, include_expired),
+        })
+    }
+
+    pub fn get_approval(
+        &self,
+        deps: Deps,
+        env: Env,
+        token_id: String,
+        spender: String,
+        include_expired: bool,
+    ) -> StdResult<ApprovalResponse> {
+        let token = self.tokens.load(deps.storage, &token_id)?;
+
+        if token.owner == spender {
+            return Ok(ApprovalResponse {
+                approval: Approval {
+                    spender: token.owner,
+                    expires: Expiration::Never {},
+                },
+            });
+        }
+
+        filter_approvals(&env, token.approvals, include_expired)
+            .into_iter()
+            .find(|approval| approval.spender == spender)
+            .map(|approval| ApprovalResponse { approval })
+            .ok_or_else(|| StdError::not_found("Approval"))
+    }
+
+    pub fn get_approvals(
+        &self,
+        deps: Deps,
+        env: Env,
+        token_id: String,
+        include_expired: bool,
+    ) -> StdResult<ApprovalsResponse> {
+        let token = self.tokens.load(deps.storage, &token_id)?;
+
+        Ok(ApprovalsResponse {
+            approvals: filter_approvals(&env, token.approvals, include_expired),
+        })
     }
 
     pub fn get_owner_tokens(
@@ -60,12 +420,35 @@ impl<'a> Contract<'a> {
         deps: Deps,
         owner: String,
         start_after: Option<String>,
-        limit: Option<u128>,
+        limit: Option<u32>,
+    ) -> StdResult<TokensResponse> {
+        let tokens = self.get_owner_tokens_info(deps, owner, start_after, limit)?;
+
+        Ok(tokens.into())
+    }
+
+    pub fn get_all_tokens(
+        &self,
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
     ) -> StdResult<TokensResponse> {
-        let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
-        let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
+        let tokens = self.get_all_tokens_info(deps, start_after, limit)?;
+
+        Ok(tokens.into())
+    }
 
-        let tokens: StdResult<Vec<Token>> = self
+    pub fn get_owner_tokens_info(
+        &self,
+        deps: Deps,
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<TokensInfoResponse<T>> {
+        let limit = page_limit(limit);
+        let start = start_after.map(Bound::exclusive);
+
+        let tokens: StdResult<Vec<Token<T>>> = self
             .tokens
             .idx
             .owner
@@ -75,39 +458,116 @@ impl<'a> Contract<'a> {
             .map(|token| token.map(|(_, token)| token))
             .collect();
 
-        Ok(TokensResponse { tokens: tokens? })
+        Ok(TokensInfoResponse::new(tokens?, limit))
     }
 
-    pub fn get_all_tokens(
+    pub fn get_all_tokens_info(
         &self,
         deps: Deps,
         start_after: Option<String>,
-        limit: Option<u128>,
-    ) -> StdResult<TokensResponse> {
-        let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
-        let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
+        limit: Option<u32>,
+    ) -> StdResult<TokensInfoResponse<T>> {
+        let limit = page_limit(limit);
+        let start = start_after.as_deref().map(Bound::exclusive);
 
-        let tokens: StdResult<Vec<Token>> = self
+        let tokens: StdResult<Vec<Token<T>>> = self
             .tokens
             .range(deps.storage, start, None, Order::Ascending)
             .take(limit)
             .map(|token| token.map(|(_, token)| token))
             .collect();
 
-        Ok(TokensResponse { tokens: tokens? })
+        Ok(TokensInfoResponse::new(tokens?, limit))
+    }
+
+    pub fn get_operator(
+        &self,
+        deps: Deps,
+        env: Env,
+        owner: String,
+        operator: String,
+        include_expired: bool,
+    ) -> StdResult<OperatorResponse> {
+        let owner = deps.api.addr_validate(&owner)?;
+        let operator = deps.api.addr_validate(&operator)?;
+
+        match self.operators.may_load(deps.storage, (&owner, &operator))? {
+            Some(expires) if include_expired || !expires.is_expired(&env.block) => {
+                Ok(OperatorResponse {
+                    approval: Approval {
+                        spender: operator,
+                        expires,
+                    },
+                })
+            }
+            _ => Err(StdError::not_found("Approval")),
+        }
+    }
+
+    pub fn get_all_operators(
+        &self,
+        deps: Deps,
+        env: Env,
+        owner: String,
+        include_expired: bool,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<OperatorsResponse> {
+        let limit = page_limit(limit);
+        let owner = deps.api.addr_validate(&owner)?;
+        let start_addr = start_after
+            .map(|addr| deps.api.addr_validate(&addr))
+            .transpose()?;
+        let start = start_addr.as_ref().map(Bound::exclusive);
+
+        let operators: StdResult<Vec<Approval>> = self
+            .operators
+            .prefix(&owner)
+            .range(deps.storage, start, None, Order::Ascending)
+            .filter(|operator| match operator {
+                Ok((_, expires)) => include_expired || !expires.is_expired(&env.block),
+                Err(_) => true,
+            })
+            .take(limit)
+            .map(|operator| operator.map(|(spender, expires)| Approval { spender, expires }))
+            .collect();
+
+        Ok(OperatorsResponse {
+            operators: operators?,
+        })
     }
 }
 
+fn page_limit(limit: Option<u32>) -> usize {
+    limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize
+}
+
+fn filter_approvals(env: &Env, approvals: Vec<Approval>, include_expired: bool) -> Vec<Approval> {
+    approvals
+        .into_iter()
+        .filter(|approval| include_expired || !approval.is_expired(&env.block))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use cosmwasm_std::{
-        testing::{MockApi, MockQuerier},
-        Env, MemoryStorage, OwnedDeps,
+        coin, coins,
+        testing::{mock_dependencies, mock_env, MockApi, MockQuerier},
+        Addr, Binary, Decimal, Env, HexBinary, MemoryStorage, OwnedDeps, Uint128,
     };
+    use cw_utils::Expiration;
 
     use crate::{
-        state::Contract,
-        utils::test_utils::{initialize_contract, mint_token, MINTER, OWNER, TOKEN_ID},
+        msg::{ExecuteMsg, InstantiateMsg, RoyaltyMsg, TokenMsg},
+        response::CollectionConfigResponse,
+        state::{
+            Contract, Eligibility, Metadata, MetadataExtension, MintPhase, PauseScope, Role, Trait,
+        },
+        utils::test_utils::{
+            get_default_instantiate_msg, get_mock_info, initialize_contract,
+            initialize_contract_with_msg, mint_token, MINTER, OWNER, TOKEN_ID,
+        },
     };
 
     #[test]
@@ -119,6 +579,346 @@ mod tests {
         insta::assert_json_snapshot!(res);
     }
 
+    #[test]
+    fn get_allowlist_minted_should_count_per_root() {
+        let (mut deps, contract, ..) = initialize_contract();
+        let minter = Addr::unchecked(MINTER);
+        let current_root = HexBinary::from([1; 32]);
+        let phase_root = HexBinary::from([2; 32]);
+        contract
+            .merkle_root
+            .save(&mut deps.storage, &current_root)
+            .unwrap();
+        contract
+            .allowlist_minted
+            .save(&mut deps.storage, (&current_root, &minter), &2)
+            .unwrap();
+        contract
+            .allowlist_minted
+            .save(&mut deps.storage, (&phase_root, &minter), &1)
+            .unwrap();
+        let minted = |deps: &OwnedDeps<MemoryStorage, MockApi, MockQuerier>, root| {
+            contract
+                .get_allowlist_minted(deps.as_ref(), MINTER.to_string(), root)
+                .unwrap()
+                .minted
+        };
+
+        assert_eq!(minted(&deps, None), 2);
+        assert_eq!(minted(&deps, Some(phase_root.clone())), 1);
+        contract.merkle_root.remove(&mut deps.storage);
+        assert_eq!(minted(&deps, None), 0);
+        assert_eq!(minted(&deps, Some(phase_root)), 1);
+    }
+
+    #[test]
+    fn get_token_flags_should_return_frozen_flags() {
+        let (mut deps, contract, env, ..) = initialize_contract();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+        contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(MINTER),
+                ExecuteMsg::FreezeToken {
+                    token_id: TOKEN_ID.to_string(),
+                },
+            )
+            .unwrap();
+
+        let res = contract
+            .get_token_flags(deps.as_ref(), TOKEN_ID.to_string())
+            .unwrap();
+
+        assert!(res.frozen);
+        assert!(!res.soulbound);
+    }
+
+    #[test]
+    fn get_token_flags_should_return_soulbound_token() {
+        let (mut deps, contract, env, ..) = initialize_contract();
+        contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(MINTER),
+                ExecuteMsg::Mint {
+                    token: TokenMsg {
+                        owner: OWNER.to_string(),
+                        token_id: Some(TOKEN_ID.to_string()),
+                        token_uri: None,
+                        royalty: None,
+                        extension: None,
+                        soulbound: true,
+                    },
+                },
+            )
+            .unwrap();
+
+        let res = contract
+            .get_token_flags(deps.as_ref(), TOKEN_ID.to_string())
+            .unwrap();
+
+        assert!(res.soulbound);
+        assert!(!res.frozen);
+        assert!(contract
+            .get_token_flags(deps.as_ref(), "missing".to_string())
+            .is_err());
+    }
+
+    #[test]
+    fn get_collection_config_should_return_instantiate_settings() {
+        let (mut deps, contract, env, ..) = initialize_contract_with_msg(InstantiateMsg {
+            auto_token_id: true,
+            max_supply: Some(10),
+            soulbound: true,
+            ..get_default_instantiate_msg()
+        });
+        contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(MINTER),
+                ExecuteMsg::FreezeMetadata {},
+            )
+            .unwrap();
+
+        let res = contract.get_collection_config(deps.as_ref()).unwrap();
+
+        assert_eq!(
+            res,
+            CollectionConfigResponse {
+                auto_token_id: true,
+                max_supply: Some(10),
+                soulbound: true,
+                metadata_frozen: true,
+            }
+        );
+    }
+
+    fn transfer_token(
+        contract: &Contract,
+        deps: &mut OwnedDeps<MemoryStorage, MockApi, MockQuerier>,
+        env: Env,
+        from: &str,
+        to: &str,
+    ) {
+        contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(from),
+                ExecuteMsg::TransferNft {
+                    token_id: TOKEN_ID.to_string(),
+                    to: to.to_string(),
+                },
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn get_token_history_should_return_chain_of_custody() {
+        let (mut deps, contract, mut env, ..) = initialize_contract();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+        env.block.height += 1;
+        transfer_token(&contract, &mut deps, env.clone(), OWNER, "first");
+        env.block.height += 1;
+        contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info("first"),
+                ExecuteMsg::SendNft {
+                    token_id: TOKEN_ID.to_string(),
+                    contract: "second".to_string(),
+                    msg: Binary::default(),
+                },
+            )
+            .unwrap();
+
+        let first_page = contract
+            .get_token_history(deps.as_ref(), TOKEN_ID.to_string(), None, Some(2))
+            .unwrap();
+        let second_page = contract
+            .get_token_history(
+                deps.as_ref(),
+                TOKEN_ID.to_string(),
+                first_page.next_start_after,
+                Some(2),
+            )
+            .unwrap();
+
+        insta::assert_json_snapshot!(first_page);
+        insta::assert_json_snapshot!(second_page);
+    }
+
+    #[test]
+    fn get_token_history_should_keep_latest_records_only() {
+        let (mut deps, contract, env, ..) = initialize_contract_with_msg(InstantiateMsg {
+            provenance_limit: Some(2),
+            ..get_default_instantiate_msg()
+        });
+        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+        transfer_token(&contract, &mut deps, env.clone(), OWNER, "first");
+        transfer_token(&contract, &mut deps, env, "first", "second");
+
+        let res = contract
+            .get_token_history(deps.as_ref(), TOKEN_ID.to_string(), None, None)
+            .unwrap();
+
+        let indexes: Vec<u64> = res.history.iter().map(|record| record.index).collect();
+        assert_eq!(indexes, vec![1, 2]);
+        assert_eq!(res.history[1].to, "second");
+    }
+
+    #[test]
+    fn get_token_history_should_be_empty_after_burn() {
+        let (mut deps, contract, env, ..) = initialize_contract();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+        contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(OWNER),
+                ExecuteMsg::Burn {
+                    token_id: TOKEN_ID.to_string(),
+                },
+            )
+            .unwrap();
+
+        let res = contract
+            .get_token_history(deps.as_ref(), TOKEN_ID.to_string(), None, None)
+            .unwrap();
+
+        assert!(res.history.is_empty());
+    }
+
+    #[test]
+    fn get_paused_should_return_paused_scope() {
+        let (mut deps, contract, env, ..) = initialize_contract();
+        let not_paused = contract.get_paused(deps.as_ref()).unwrap();
+        contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(OWNER),
+                ExecuteMsg::Pause {
+                    scope: Some(PauseScope::Transfers),
+                },
+            )
+            .unwrap();
+
+        let paused = contract.get_paused(deps.as_ref()).unwrap();
+
+        assert_eq!(not_paused.scope, None);
+        assert_eq!(paused.scope, Some(PauseScope::Transfers));
+    }
+
+    #[test]
+    fn get_balance_should_return_contract_funds() {
+        let (mut deps, contract, env, ..) = initialize_contract();
+        deps.querier
+            .update_balance(env.contract.address.clone(), coins(300, "umlg"));
+
+        let res = contract.get_balance(deps.as_ref(), env).unwrap();
+        let sale = contract.get_sale(deps.as_ref()).unwrap();
+
+        assert_eq!(res.amount, coins(300, "umlg"));
+        assert_eq!(sale.sale, None);
+    }
+
+    #[test]
+    fn get_current_phase_should_follow_block_time() {
+        let (mut deps, contract, mut env, ..) = initialize_contract_with_msg(InstantiateMsg {
+            auto_token_id: true,
+            ..get_default_instantiate_msg()
+        });
+        let phase = MintPhase {
+            name: "public".to_string(),
+            start_time: env.block.time.plus_seconds(10),
+            end_time: env.block.time.plus_seconds(20),
+            price: coin(100, "umlg"),
+            per_wallet_limit: 1,
+            eligibility: Eligibility::Public,
+        };
+        contract
+            .execute(
+                deps.as_mut(),
+                env.clone(),
+                get_mock_info(OWNER),
+                ExecuteMsg::UpdatePhases {
+                    phases: vec![phase.clone()],
+                },
+            )
+            .unwrap();
+
+        let before = contract
+            .get_current_phase(deps.as_ref(), env.clone())
+            .unwrap();
+        env.block.time = env.block.time.plus_seconds(10);
+        let during = contract
+            .get_current_phase(deps.as_ref(), env.clone())
+            .unwrap();
+        env.block.time = env.block.time.plus_seconds(10);
+        let after = contract.get_current_phase(deps.as_ref(), env).unwrap();
+
+        assert_eq!(before.phase, None);
+        assert_eq!(during.phase, Some(phase));
+        assert_eq!(after.phase, None);
+    }
+
+    #[test]
+    fn get_has_role_should_return_membership() {
+        let (deps, contract, ..) = initialize_contract();
+
+        let minter = contract
+            .get_has_role(deps.as_ref(), Role::Minter, MINTER.to_string())
+            .unwrap();
+        let owner = contract
+            .get_has_role(deps.as_ref(), Role::Minter, OWNER.to_string())
+            .unwrap();
+
+        assert!(minter.has_role);
+        assert!(!owner.has_role);
+    }
+
+    #[test]
+    fn get_role_members_should_paginate_members() {
+        let (mut deps, contract, env, ..) = initialize_contract();
+        for address in ["minter2", "minter3"] {
+            contract
+                .execute(
+                    deps.as_mut(),
+                    env.clone(),
+                    get_mock_info(OWNER),
+                    ExecuteMsg::GrantRole {
+                        role: Role::Minter,
+                        address: address.to_string(),
+                    },
+                )
+                .unwrap();
+        }
+
+        let first_page = contract
+            .get_role_members(deps.as_ref(), Role::Minter, None, Some(2))
+            .unwrap();
+        let second_page = contract
+            .get_role_members(
+                deps.as_ref(),
+                Role::Minter,
+                Some("minter2".to_string()),
+                None,
+            )
+            .unwrap();
+        let pausers = contract
+            .get_role_members(deps.as_ref(), Role::Pauser, None, None)
+            .unwrap();
+
+        assert_eq!(first_page.members, vec![MINTER, "minter2"]);
+        assert_eq!(second_page.members, vec!["minter3"]);
+        assert_eq!(pausers.members, vec![OWNER]);
+    }
+
     #[test]
     fn get_nft_info_should_return_nft_info() {
         let (mut deps, contract, env, ..) = initialize_contract();
@@ -131,6 +931,55 @@ mod tests {
         insta::assert_json_snapshot!(res);
     }
 
+    #[test]
+    fn get_nft_info_should_return_on_chain_metadata() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let contract = Contract::<MetadataExtension>::get_contract();
+        contract
+            .instantiate(
+                deps.as_mut(),
+                env.clone(),
+                get_mock_info(OWNER),
+                get_default_instantiate_msg(),
+            )
+            .unwrap();
+
+        let extension = Some(Metadata {
+            name: Some("Token #1".to_string()),
+            description: Some("The very first token".to_string()),
+            image: Some("ipfs://image".to_string()),
+            attributes: Some(vec![Trait {
+                display_type: None,
+                trait_type: "background".to_string(),
+                value: "blue".to_string(),
+            }]),
+        });
+        contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(MINTER),
+                ExecuteMsg::Mint {
+                    token: TokenMsg {
+                        owner: OWNER.to_string(),
+                        token_id: Some(TOKEN_ID.to_string()),
+                        token_uri: None,
+                        royalty: None,
+                        extension,
+                        soulbound: false,
+                    },
+                },
+            )
+            .unwrap();
+
+        let res = contract
+            .get_nft_info(deps.as_ref(), TOKEN_ID.to_string())
+            .unwrap();
+
+        insta::assert_json_snapshot!(res);
+    }
+
     #[test]
     fn get_num_tokens_should_return_number_of_tokens() {
         let (mut deps, contract, env, ..) = initialize_contract();
@@ -147,7 +996,7 @@ mod tests {
         mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
 
         let res = contract
-            .get_owner_of_token(deps.as_ref(), TOKEN_ID.to_string())
+            .get_owner_of_token(deps.as_ref(), env, TOKEN_ID.to_string(), false)
             .unwrap();
 
         insta::assert_json_snapshot!(res);
@@ -199,6 +1048,278 @@ mod tests {
         insta::assert_json_snapshot!(res);
     }
 
+    #[test]
+    fn get_owner_tokens_should_page_with_cursor() {
+        let (mut deps, contract, env, ..) = initialize_contract();
+        for token_id in ["a", "b", "c", "d", "e"] {
+            mint_token(&contract, &mut deps, env.clone(), MINTER, token_id).unwrap();
+            mint_token_to(
+                &contract,
+                &mut deps,
+                env.clone(),
+                "another",
+                &format!("{token_id}1"),
+            );
+        }
+
+        let mut token_ids = vec![];
+        let mut start_after = None;
+        loop {
+            let res = contract
+                .get_owner_tokens_info(deps.as_ref(), OWNER.to_string(), start_after, Some(2))
+                .unwrap();
+            token_ids.extend(res.tokens.into_iter().map(|token| token.token_id));
+            start_after = res.next_start_after;
+            if start_after.is_none() {
+                break;
+            }
+        }
+
+        assert_eq!(token_ids, vec!["a", "b", "c", "d", "e"]);
+    }
+
+    #[test]
+    fn get_all_tokens_should_apply_default_and_max_limit() {
+        let (mut deps, contract, env, ..) = initialize_contract();
+        for token_id in 0..150 {
+            mint_token(
+                &contract,
+                &mut deps,
+                env.clone(),
+                MINTER,
+                &format!("{token_id:03}"),
+            )
+            .unwrap();
+        }
+
+        let res = contract.get_all_tokens(deps.as_ref(), None, None).unwrap();
+        assert_eq!(res.tokens.len(), 10);
+        assert_eq!(res.tokens.last().unwrap(), "009");
+
+        let res = contract
+            .get_all_tokens(deps.as_ref(), None, Some(u32::MAX))
+            .unwrap();
+        assert_eq!(res.tokens.len(), 100);
+        assert_eq!(res.tokens.last().unwrap(), "099");
+
+        let res = contract
+            .get_all_tokens_info(deps.as_ref(), Some("099".to_string()), Some(u32::MAX))
+            .unwrap();
+        assert_eq!(res.tokens.len(), 50);
+        assert_eq!(res.next_start_after, None);
+    }
+
+    #[test]
+    fn get_owner_tokens_info_should_return_owner_tokens() {
+        let (mut deps, contract, env, ..) = initialize_contract();
+        mint_multiple_tokens(&contract, &mut deps, env.clone());
+
+        let res = contract
+            .get_owner_tokens_info(deps.as_ref(), OWNER.to_string(), None, None)
+            .unwrap();
+
+        insta::assert_json_snapshot!(res);
+    }
+
+    #[test]
+    fn get_minter_should_return_minter() {
+        let (deps, contract, ..) = initialize_contract();
+
+        let res = contract.get_minter(deps.as_ref()).unwrap();
+
+        assert_eq!(res.minter, MINTER);
+    }
+
+    #[test]
+    fn get_all_nft_info_should_return_owner_and_nft_info() {
+        let (mut deps, contract, env, ..) = initialize_contract();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+
+        let res = contract
+            .get_all_nft_info(deps.as_ref(), env, TOKEN_ID.to_string(), false)
+            .unwrap();
+
+        insta::assert_json_snapshot!(res);
+    }
+
+    #[test]
+    fn get_approvals_should_filter_expired_approvals() {
+        let (mut deps, contract, mut env, ..) = initialize_contract();
+        mint_token(&contract, &mut deps, env.clone(), MINTER, TOKEN_ID).unwrap();
+        approve(&contract, &mut deps, env.clone(), "spender1", None);
+        approve(
+            &contract,
+            &mut deps,
+            env.clone(),
+            "spender2",
+            Some(Expiration::AtHeight(env.block.height + 1)),
+        );
+
+        env.block.height += 1;
+        let res = contract
+            .get_approvals(deps.as_ref(), env.clone(), TOKEN_ID.to_string(), false)
+            .unwrap();
+        assert_eq!(res.approvals.len(), 1);
+        assert_eq!(res.approvals[0].spender, "spender1");
+
+        let res = contract.get_approval(
+            deps.as_ref(),
+            env.clone(),
+            TOKEN_ID.to_string(),
+            "spender2".to_string(),
+            false,
+        );
+        assert!(res.is_err());
+
+        let res = contract
+            .get_approval(
+                deps.as_ref(),
+                env,
+                TOKEN_ID.to_string(),
+                "spender2".to_string(),
+                true,
+            )
+            .unwrap();
+        assert_eq!(res.approval.spender, "spender2");
+    }
+
+    #[test]
+    fn get_royalty_info_should_return_nothing_without_royalty() {
+        let (mut deps, contract, env, ..) = initialize_contract();
+        mint_token(&contract, &mut deps, env, MINTER, TOKEN_ID).unwrap();
+
+        let res = contract
+            .get_royalty_info(deps.as_ref(), TOKEN_ID.to_string(), Uint128::new(1000))
+            .unwrap();
+
+        assert_eq!(res.address, "");
+        assert_eq!(res.royalty_amount, Uint128::zero());
+    }
+
+    #[test]
+    fn get_royalty_info_should_return_default_royalty() {
+        let (mut deps, contract, env, ..) = initialize_contract_with_msg(InstantiateMsg {
+            royalty: Some(RoyaltyMsg {
+                payment_address: "creator".to_string(),
+                share: Decimal::percent(5),
+            }),
+            ..get_default_instantiate_msg()
+        });
+        mint_token(&contract, &mut deps, env, MINTER, TOKEN_ID).unwrap();
+
+        let res = contract
+            .get_royalty_info(deps.as_ref(), TOKEN_ID.to_string(), Uint128::new(1000))
+            .unwrap();
+
+        assert_eq!(res.address, "creator");
+        assert_eq!(res.royalty_amount, Uint128::new(50));
+    }
+
+    #[test]
+    fn get_royalty_info_should_prefer_token_royalty() {
+        let (mut deps, contract, env, ..) = initialize_contract_with_msg(InstantiateMsg {
+            royalty: Some(RoyaltyMsg {
+                payment_address: "creator".to_string(),
+                share: Decimal::percent(5),
+            }),
+            ..get_default_instantiate_msg()
+        });
+        contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(MINTER),
+                ExecuteMsg::Mint {
+                    token: TokenMsg {
+                        owner: OWNER.to_string(),
+                        token_id: Some(TOKEN_ID.to_string()),
+                        token_uri: None,
+                        royalty: Some(RoyaltyMsg {
+                            payment_address: "artist".to_string(),
+                            share: Decimal::one(),
+                        }),
+                        extension: None,
+                        soulbound: false,
+                    },
+                },
+            )
+            .unwrap();
+
+        let res = contract
+            .get_royalty_info(deps.as_ref(), TOKEN_ID.to_string(), Uint128::MAX)
+            .unwrap();
+
+        assert_eq!(res.address, "artist");
+        assert_eq!(res.royalty_amount, Uint128::MAX);
+    }
+
+    #[test]
+    fn get_operator_should_fail_when_operator_expired() {
+        let (mut deps, contract, mut env, ..) = initialize_contract();
+        approve_all(
+            &contract,
+            &mut deps,
+            env.clone(),
+            "operator",
+            Some(Expiration::AtHeight(env.block.height + 1)),
+        );
+
+        env.block.height += 1;
+        let res = contract.get_operator(
+            deps.as_ref(),
+            env.clone(),
+            OWNER.to_string(),
+            "operator".to_string(),
+            false,
+        );
+        assert!(res.is_err());
+
+        let res = contract
+            .get_operator(
+                deps.as_ref(),
+                env,
+                OWNER.to_string(),
+                "operator".to_string(),
+                true,
+            )
+            .unwrap();
+        assert_eq!(res.approval.spender, "operator");
+    }
+
+    #[test]
+    fn get_all_operators_should_return_operators_page() {
+        let (mut deps, contract, env, ..) = initialize_contract();
+        approve_all(&contract, &mut deps, env.clone(), "operator1", None);
+        approve_all(&contract, &mut deps, env.clone(), "operator2", None);
+        approve_all(&contract, &mut deps, env.clone(), "operator3", None);
+
+        let res = contract
+            .get_all_operators(
+                deps.as_ref(),
+                env.clone(),
+                OWNER.to_string(),
+                false,
+                None,
+                Some(2),
+            )
+            .unwrap();
+        assert_eq!(res.operators.len(), 2);
+        assert_eq!(res.operators[1].spender, "operator2");
+
+        let res = contract
+            .get_all_operators(
+                deps.as_ref(),
+                env,
+                OWNER.to_string(),
+                false,
+                Some("operator2".to_string()),
+                None,
+            )
+            .unwrap();
+        assert_eq!(res.operators.len(), 1);
+        assert_eq!(res.operators[0].spender, "operator3");
+    }
+
     fn mint_multiple_tokens(
         contract: &Contract,
         deps: &mut OwnedDeps<MemoryStorage, MockApi, MockQuerier>,
@@ -208,4 +1329,71 @@ mod tests {
         mint_token(contract, deps, env.clone(), MINTER, "2").unwrap();
         mint_token(contract, deps, env.clone(), MINTER, "3").unwrap();
     }
+
+    fn approve_all(
+        contract: &Contract,
+        deps: &mut OwnedDeps<MemoryStorage, MockApi, MockQuerier>,
+        env: Env,
+        operator: &str,
+        expires: Option<Expiration>,
+    ) {
+        contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(OWNER),
+                ExecuteMsg::ApproveAll {
+                    operator: operator.to_string(),
+                    expires,
+                },
+            )
+            .unwrap();
+    }
+
+    fn approve(
+        contract: &Contract,
+        deps: &mut OwnedDeps<MemoryStorage, MockApi, MockQuerier>,
+        env: Env,
+        spender: &str,
+        expires: Option<Expiration>,
+    ) {
+        contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(OWNER),
+                ExecuteMsg::Approve {
+                    spender: spender.to_string(),
+                    token_id: TOKEN_ID.to_string(),
+                    expires,
+                },
+            )
+            .unwrap();
+    }
+
+    fn mint_token_to(
+        contract: &Contract,
+        deps: &mut OwnedDeps<MemoryStorage, MockApi, MockQuerier>,
+        env: Env,
+        owner: &str,
+        token_id: &str,
+    ) {
+        contract
+            .execute(
+                deps.as_mut(),
+                env,
+                get_mock_info(MINTER),
+                ExecuteMsg::Mint {
+                    token: TokenMsg {
+                        owner: owner.to_string(),
+                        token_id: Some(token_id.to_string()),
+                        token_uri: None,
+                        royalty: None,
+                        extension: None,
+                        soulbound: false,
+                    },
+                },
+            )
+            .unwrap();
+    }
 }
@@ -0,0 +1,207 @@
+//! Receiving side of `SendNft`, for contracts that accept tokens of cw721 collections.
+
+use cosmwasm_std::{
+    from_binary, Addr, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+};
+use cw721::Cw721ReceiveMsg;
+use serde::de::DeserializeOwned;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ReceiverError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Collection not allowed: {collection}")]
+    CollectionNotAllowed { collection: String },
+}
+
+/// Token handed over by `SendNft`, with the inner message already decoded
+pub struct ReceivedNft<M> {
+    /// cw721 contract that sent the callback
+    pub collection: Addr,
+    /// Previous owner of the token, who called `SendNft`
+    pub sender: Addr,
+    pub token_id: String,
+    pub msg: M,
+}
+
+pub trait Cw721Receiver {
+    /// Inner message sent along with the token in `SendNft`
+    type Msg: DeserializeOwned;
+    type Error: From<ReceiverError>;
+
+    /// Only allowed collections may send tokens, the callback of any other contract is rejected
+    fn is_allowed_collection(&self, deps: Deps, collection: &Addr) -> StdResult<bool>;
+
+    fn on_receive(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        nft: ReceivedNft<Self::Msg>,
+    ) -> Result<Response, Self::Error>;
+
+    /// Handles `ReceiveNft`, whose sender is the collection of the token
+    fn receive_nft(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: Cw721ReceiveMsg,
+    ) -> Result<Response, Self::Error> {
+        if !self
+            .is_allowed_collection(deps.as_ref(), &info.sender)
+            .map_err(ReceiverError::from)?
+        {
+            return Err(ReceiverError::CollectionNotAllowed {
+                collection: info.sender.into_string(),
+            }
+            .into());
+        }
+
+        let nft = ReceivedNft {
+            collection: info.sender,
+            sender: deps
+                .api
+                .addr_validate(&msg.sender)
+                .map_err(ReceiverError::from)?,
+            token_id: msg.token_id,
+            msg: from_binary(&msg.msg).map_err(ReceiverError::from)?,
+        };
+
+        self.on_receive(deps, env, nft)
+    }
+}
+
+/// Example receiver: a vault that keeps the tokens of a fixed set of collections
+/// and remembers who deposited them.
+pub mod example {
+    use cosmwasm_schema::{cw_serde, QueryResponses};
+    use cosmwasm_std::{
+        to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+    };
+    use cw721::Cw721ReceiveMsg;
+    use cw_events::ActionEvent;
+    use cw_storage_plus::Map;
+
+    use super::{Cw721Receiver, ReceivedNft, ReceiverError};
+
+    #[cw_serde]
+    pub struct InstantiateMsg {
+        pub collections: Vec<String>,
+    }
+
+    #[cw_serde]
+    pub enum ExecuteMsg {
+        ReceiveNft(Cw721ReceiveMsg),
+    }
+
+    /// Inner message of `SendNft`
+    #[cw_serde]
+    pub enum VaultMsg {
+        Deposit { memo: Option<String> },
+    }
+
+    #[cw_serde]
+    #[derive(QueryResponses)]
+    pub enum QueryMsg {
+        #[returns(Option<Deposit>)]
+        Deposit {
+            collection: String,
+            token_id: String,
+        },
+    }
+
+    #[cw_serde]
+    pub struct Deposit {
+        pub depositor: Addr,
+        pub memo: Option<String>,
+    }
+
+    pub struct Vault<'a> {
+        pub collections: Map<'a, &'a Addr, ()>,
+        /// (collection, token_id) of every deposited token
+        pub deposits: Map<'a, (&'a Addr, &'a str), Deposit>,
+    }
+
+    impl<'a> Default for Vault<'a> {
+        fn default() -> Self {
+            Self {
+                collections: Map::new("collections"),
+                deposits: Map::new("deposits"),
+            }
+        }
+    }
+
+    impl<'a> Cw721Receiver for Vault<'a> {
+        type Msg = VaultMsg;
+        type Error = ReceiverError;
+
+        fn is_allowed_collection(&self, deps: Deps, collection: &Addr) -> StdResult<bool> {
+            Ok(self.collections.has(deps.storage, collection))
+        }
+
+        fn on_receive(
+            &self,
+            deps: DepsMut,
+            _env: Env,
+            nft: ReceivedNft<VaultMsg>,
+        ) -> Result<Response, ReceiverError> {
+            let VaultMsg::Deposit { memo } = nft.msg;
+            let deposit = Deposit {
+                depositor: nft.sender,
+                memo,
+            };
+            self.deposits
+                .save(deps.storage, (&nft.collection, &nft.token_id), &deposit)?;
+
+            Ok(ActionEvent::new("deposit")
+                .sender(deposit.depositor)
+                .collection(nft.collection)
+                .token_id(nft.token_id)
+                .into_response())
+        }
+    }
+
+    pub fn instantiate(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        msg: InstantiateMsg,
+    ) -> StdResult<Response> {
+        let vault = Vault::default();
+        for collection in msg.collections {
+            let collection = deps.api.addr_validate(&collection)?;
+            vault.collections.save(deps.storage, &collection, &())?;
+        }
+
+        Ok(Response::default())
+    }
+
+    pub fn execute(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: ExecuteMsg,
+    ) -> Result<Response, ReceiverError> {
+        match msg {
+            ExecuteMsg::ReceiveNft(msg) => Vault::default().receive_nft(deps, env, info, msg),
+        }
+    }
+
+    pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+        match msg {
+            QueryMsg::Deposit {
+                collection,
+                token_id,
+            } => {
+                let collection = deps.api.addr_validate(&collection)?;
+                to_binary(
+                    &Vault::default()
+                        .deposits
+                        .may_load(deps.storage, (&collection, &token_id))?,
+                )
+            }
+        }
+    }
+}
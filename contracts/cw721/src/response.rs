@@ -1,16 +1,28 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Coin, HexBinary, Uint128};
+use cw_utils::Expiration;
 
-use crate::state::Token;
+use crate::state::{Approval, MintPhase, PauseScope, Provenance, SaleConfig, Token};
 
 #[cw_serde]
 pub struct OwnerOfResponse {
     pub owner: Addr,
+    pub approvals: Vec<Approval>,
+}
+
+#[cw_serde]
+pub struct ApprovalResponse {
+    pub approval: Approval,
+}
+
+#[cw_serde]
+pub struct ApprovalsResponse {
+    pub approvals: Vec<Approval>,
 }
 
 #[cw_serde]
 pub struct NumTokensResponse {
-    pub number: u128,
+    pub count: u64,
 }
 
 #[cw_serde]
@@ -20,11 +32,182 @@ pub struct ContractInfoResponse {
 }
 
 #[cw_serde]
-pub struct NftInfoResponse {
+pub struct CollectionConfigResponse {
+    /// Ids are assigned by the contract on mint
+    pub auto_token_id: bool,
+    pub max_supply: Option<u64>,
+    /// Every token of the collection is non-transferable
+    pub soulbound: bool,
+    /// The metadata of the whole collection can no longer be changed
+    pub metadata_frozen: bool,
+}
+
+#[cw_serde]
+pub struct TokenFlagsResponse {
+    /// The metadata of this token can no longer be changed
+    pub frozen: bool,
+    /// The token cannot be transferred, on its own or as part of a soulbound collection
+    pub soulbound: bool,
+}
+
+/// Response data of `Mint`
+#[cw_serde]
+pub struct MintResponse {
+    pub token_id: String,
+}
+
+/// Response data of `MintBatch`
+#[cw_serde]
+pub struct MintBatchResponse {
+    pub token_ids: Vec<String>,
+}
+
+#[cw_serde]
+pub struct NftInfoResponse<T> {
     pub token_uri: Option<String>,
+    pub extension: T,
+}
+
+#[cw_serde]
+pub struct AllNftInfoResponse<T> {
+    pub access: OwnerOfResponse,
+    pub info: NftInfoResponse<T>,
 }
 
 #[cw_serde]
 pub struct TokensResponse {
-    pub tokens: Vec<Token>,
+    /// The last id of a full page is the `start_after` of the next page
+    pub tokens: Vec<String>,
+}
+
+#[cw_serde]
+pub struct TokensInfoResponse<T> {
+    pub tokens: Vec<Token<T>>,
+    /// `start_after` of the next page, `None` on the last page
+    pub next_start_after: Option<String>,
+}
+
+impl<T> TokensInfoResponse<T> {
+    /// Builds a page of at most `limit` tokens, a full page may be followed by another one
+    pub fn new(tokens: Vec<Token<T>>, limit: usize) -> Self {
+        let next_start_after = match tokens.last() {
+            Some(last) if tokens.len() == limit => Some(last.token_id.clone()),
+            _ => None,
+        };
+
+        Self {
+            tokens,
+            next_start_after,
+        }
+    }
+}
+
+impl<T> From<TokensInfoResponse<T>> for TokensResponse {
+    fn from(response: TokensInfoResponse<T>) -> Self {
+        Self {
+            tokens: response
+                .tokens
+                .into_iter()
+                .map(|token| token.token_id)
+                .collect(),
+        }
+    }
+}
+
+#[cw_serde]
+pub struct MinterResponse {
+    pub minter: Addr,
+}
+
+#[cw_serde]
+pub struct OwnershipResponse {
+    pub owner: Option<Addr>,
+    pub pending_owner: Option<Addr>,
+    pub pending_expiry: Option<Expiration>,
+}
+
+#[cw_serde]
+pub struct TokenHistoryResponse {
+    pub history: Vec<Provenance>,
+    pub next_start_after: Option<u64>,
+}
+
+impl TokenHistoryResponse {
+    /// Builds a page of at most `limit` records, a full page may be followed by another one
+    pub fn new(history: Vec<Provenance>, limit: usize) -> Self {
+        let next_start_after = match history.last() {
+            Some(last) if history.len() == limit => Some(last.index),
+            _ => None,
+        };
+
+        Self {
+            history,
+            next_start_after,
+        }
+    }
+}
+
+#[cw_serde]
+pub struct PausedResponse {
+    /// Operations that are halted, `None` if the contract is not paused
+    pub scope: Option<PauseScope>,
+}
+
+#[cw_serde]
+pub struct OperatorResponse {
+    pub approval: Approval,
+}
+
+#[cw_serde]
+pub struct SaleResponse {
+    /// `None` while the public mint is closed
+    pub sale: Option<SaleConfig>,
+}
+
+#[cw_serde]
+pub struct MerkleRootResponse {
+    /// `None` while the allowlist mint is closed
+    pub root: Option<HexBinary>,
+}
+
+#[cw_serde]
+pub struct AllowlistMintedResponse {
+    pub minted: u32,
+}
+
+#[cw_serde]
+pub struct CurrentPhaseResponse {
+    /// `None` between phases and outside of the schedule
+    pub phase: Option<MintPhase>,
+}
+
+#[cw_serde]
+pub struct BalanceResponse {
+    pub amount: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct HasRoleResponse {
+    pub has_role: bool,
+}
+
+#[cw_serde]
+pub struct RoleMembersResponse {
+    pub members: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct OperatorsResponse {
+    pub operators: Vec<Approval>,
+}
+
+#[cw_serde]
+pub struct RoyaltiesInfoResponse {
+    pub address: String,
+    pub royalty_amount: Uint128,
+}
+
+#[cw_serde]
+pub struct CheckRoyaltiesResponse {
+    pub royalty_payments: bool,
 }
@@ -1,51 +1,462 @@
-use cosmwasm_std::Addr;
-use cw_storage_plus::{Index, IndexList, IndexedMap, Item, MultiIndex};
+use cosmwasm_std::{
+    Addr, BlockInfo, Coin, Decimal, Empty, HexBinary, Order, OverflowError, OverflowOperation,
+    StdError, StdResult, Storage, Timestamp,
+};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
+use cw_utils::Expiration;
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
-
-use crate::response::ContractInfoResponse;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 pub const CONTRACT_NAME: &str = "contract228";
 pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 pub const TOKENS_PK: &str = "tokens_key";
+/// Key of the collection info before it moved away from the cw2 version key
+pub const LEGACY_CONTRACT_INFO_KEY: &str = "contract_info";
+pub const DEFAULT_PROVENANCE_LIMIT: u32 = 20;
+pub const DEFAULT_TOKEN_ID_MAX_LENGTH: u32 = 128;
+
+/// Extension of the default contract, tokens carry no on-chain metadata
+pub type Extension = Option<Empty>;
+
+/// Extension for collections that keep their metadata on-chain
+pub type MetadataExtension = Option<Metadata>;
 
-pub struct Contract<'a> {
+pub struct Contract<'a, T = Extension>
+where
+    T: Serialize + DeserializeOwned + Clone,
+{
+    /// Removed once the owner renounces the ownership
     pub owner: Item<'a, Addr>,
-    pub contract_info: Item<'a, ContractInfoResponse>,
+    pub pending_owner: Item<'a, PendingOwner>,
+    pub contract_info: Item<'a, CollectionInfo>,
+    /// Minter reported by the `Minter` query, other accounts may hold the minter role too
     pub minter: Item<'a, Addr>,
-    pub tokens: IndexedMap<'a, &'a str, Token, TokenIndex<'a>>,
+    /// Members of each role, keyed by (role, member)
+    pub roles: Map<'a, (&'a str, &'a Addr), ()>,
+    /// Collection-wide royalty, used for tokens without their own royalty
+    pub default_royalty: Item<'a, Royalty>,
+    pub tokens: IndexedMap<'a, &'a str, Token<T>, TokenIndex<'a, T>>,
+    /// Total supply, kept next to `tokens` so that it never has to be counted
+    pub token_count: Item<'a, u64>,
+    /// Next id handed out when the collection assigns ids itself
+    pub next_token_id: Item<'a, u64>,
+    /// (owner, operator) pairs allowed to manage every token of the owner
+    pub operators: Map<'a, (&'a Addr, &'a Addr), Expiration>,
+    /// Receivers that get one callback per `BatchSendNft` instead of one per token
+    pub batch_receivers: Map<'a, &'a Addr, ()>,
+    /// Tokens whose metadata can no longer be changed
+    pub frozen_tokens: Map<'a, &'a str, ()>,
+    /// Locks the metadata of every token once set
+    pub metadata_frozen: Item<'a, bool>,
+    /// Tokens minted as non-transferable in a transferable collection
+    pub soulbound_tokens: Map<'a, &'a str, ()>,
+    /// Set while the owner has halted part of the contract
+    pub paused: Item<'a, PauseScope>,
+    /// Chain of custody per token, keyed by (token_id, index)
+    pub provenance: Map<'a, (&'a str, u64), Provenance>,
+    /// Number of the latest provenance records kept per token, older ones are pruned
+    pub provenance_limit: Item<'a, u32>,
+    /// Format of the token ids given on mint, defaults apply when missing
+    pub token_id_rules: Item<'a, TokenIdRules>,
+    /// Terms of the public mint, closed while missing
+    pub sale: Item<'a, SaleConfig>,
+    /// Root of the Merkle allowlist, closed while missing
+    pub merkle_root: Item<'a, HexBinary>,
+    /// Tokens minted by each allowlisted address, keyed by (root, address). `AllowlistMint`
+    /// and the allowlist phases of the same root share the count, so an allocation is only
+    /// minted once
+    pub allowlist_minted: Map<'a, (&'a [u8], &'a Addr), u32>,
+    /// Mint schedule, ordered by time and not overlapping
+    pub phases: Item<'a, Vec<MintPhase>>,
+    /// Tokens minted by each wallet, keyed by (index of the phase, wallet)
+    pub phase_minted: Map<'a, (u32, &'a Addr), u32>,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug, JsonSchema)]
-pub struct Token {
+pub struct Token<T> {
     pub owner: Addr,
     pub token_id: String,
     pub token_uri: Option<String>,
+    /// Tokens minted before approvals existed are stored without this field
+    #[serde(default)]
+    pub approvals: Vec<Approval>,
+    pub royalty: Option<Royalty>,
+    pub extension: T,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, JsonSchema)]
+pub struct Royalty {
+    pub payment_address: Addr,
+    /// Fraction of the sale price, e.g. `Decimal::percent(5)` is 5%
+    pub share: Decimal,
+}
+
+/// On-chain metadata following the OpenSea metadata standard
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, JsonSchema, Default)]
+pub struct Metadata {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub image: Option<String>,
+    pub attributes: Option<Vec<Trait>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, JsonSchema)]
+pub struct Trait {
+    pub display_type: Option<String>,
+    pub trait_type: String,
+    pub value: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, JsonSchema)]
+pub struct Provenance {
+    /// Position in the token history, starting from 0 at mint
+    pub index: u64,
+    pub kind: ProvenanceKind,
+    /// Empty for mints
+    pub from: Option<Addr>,
+    pub to: Addr,
+    pub height: u64,
+    pub time: Timestamp,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProvenanceKind {
+    Mint,
+    Transfer,
+    Send,
+}
+
+/// Operations halted by `Pause`
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PauseScope {
+    Minting,
+    Transfers,
+    All,
+}
+
+impl PauseScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PauseScope::Minting => "minting",
+            PauseScope::Transfers => "transfers",
+            PauseScope::All => "all",
+        }
+    }
+}
+
+/// Terms of `PublicMint`, the proceeds stay in the contract until withdrawn
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug, JsonSchema)]
+pub struct SaleConfig {
+    /// Price of one token
+    pub price: Coin,
+    /// Number of tokens one message can mint
+    pub per_tx_limit: u32,
+    pub start_time: Timestamp,
+}
+
+/// Window of `PhaseMint`, from `start_time` up to, but not including, `end_time`
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug, JsonSchema)]
+pub struct MintPhase {
+    pub name: String,
+    pub start_time: Timestamp,
+    pub end_time: Timestamp,
+    /// Price of one token
+    pub price: Coin,
+    /// Number of tokens one wallet can mint during the phase
+    pub per_wallet_limit: u32,
+    pub eligibility: Eligibility,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Eligibility {
+    Public,
+    /// Wallets of the Merkle allowlist with this root, their allocation lowers the wallet limit
+    Allowlist {
+        root: HexBinary,
+    },
+}
+
+/// Permissions that can be granted to several accounts
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    /// Grants and revokes roles, held by the owner
+    Admin,
+    Minter,
+    /// Updates and freezes token metadata
+    MetadataEditor,
+    Pauser,
+}
+
+impl Role {
+    /// Roles of the owner, handed over along with the ownership
+    pub const OWNER_ROLES: [Role; 2] = [Role::Admin, Role::Pauser];
+    /// Roles of the minter, handed over by `UpdateMinter`
+    pub const MINTER_ROLES: [Role; 2] = [Role::Minter, Role::MetadataEditor];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Admin => "admin",
+            Role::Minter => "minter",
+            Role::MetadataEditor => "metadata_editor",
+            Role::Pauser => "pauser",
+        }
+    }
+}
+
+/// Format of the token ids given on mint
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug, JsonSchema)]
+pub struct TokenIdRules {
+    pub max_length: u32,
+    pub charset: TokenIdCharset,
+    /// Only accepts decimal numbers without leading zeros
+    #[serde(default)]
+    pub numeric_only: bool,
+}
+
+impl Default for TokenIdRules {
+    fn default() -> Self {
+        Self {
+            max_length: DEFAULT_TOKEN_ID_MAX_LENGTH,
+            charset: TokenIdCharset::Printable,
+            numeric_only: false,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenIdCharset {
+    /// Any character except control characters
+    Printable,
+    /// ASCII letters, digits and `-_.~`, kept as is in URLs
+    UrlSafe,
+}
+
+impl TokenIdCharset {
+    pub fn contains(&self, c: char) -> bool {
+        match self {
+            TokenIdCharset::Printable => !c.is_control(),
+            TokenIdCharset::UrlSafe => c.is_ascii_alphanumeric() || "-_.~".contains(c),
+        }
+    }
+}
+
+/// Collection settings fixed at instantiate, `ContractInfo` only reports the name and the symbol
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, JsonSchema)]
+pub struct CollectionInfo {
+    pub name: String,
+    pub symbol: String,
+    #[serde(default)]
+    pub auto_token_id: bool,
+    pub max_supply: Option<u64>,
+    #[serde(default)]
+    pub soulbound: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, JsonSchema)]
+pub struct PendingOwner {
+    pub new_owner: Addr,
+    pub expiry: Expiration,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, JsonSchema)]
+pub struct Approval {
+    pub spender: Addr,
+    pub expires: Expiration,
+}
+
+impl Approval {
+    pub fn is_expired(&self, block: &BlockInfo) -> bool {
+        self.expires.is_expired(block)
+    }
 }
 
-pub struct TokenIndex<'a> {
-    pub owner: MultiIndex<'a, Addr, Token, String>,
+pub struct TokenIndex<'a, T>
+where
+    T: Serialize + DeserializeOwned + Clone,
+{
+    pub owner: MultiIndex<'a, Addr, Token<T>, String>,
 }
 
-impl<'a> IndexList<Token> for TokenIndex<'_> {
-    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Token>> + '_> {
-        let v: Vec<&dyn Index<Token>> = vec![&self.owner];
+impl<T> IndexList<Token<T>> for TokenIndex<'_, T>
+where
+    T: Serialize + DeserializeOwned + Clone,
+{
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Token<T>>> + '_> {
+        let v: Vec<&dyn Index<Token<T>>> = vec![&self.owner];
 
         Box::new(v.into_iter())
     }
 }
 
-impl<'a> Contract<'a> {
+impl<'a, T> Contract<'a, T>
+where
+    T: Serialize + DeserializeOwned + Clone,
+{
+    pub fn token_count(&self, storage: &dyn Storage) -> StdResult<u64> {
+        Ok(self.token_count.may_load(storage)?.unwrap_or_default())
+    }
+
+    pub fn increment_tokens(&self, storage: &mut dyn Storage) -> StdResult<u64> {
+        let count = self.token_count(storage)? + 1;
+        self.token_count.save(storage, &count)?;
+
+        Ok(count)
+    }
+
+    pub fn metadata_frozen(&self, storage: &dyn Storage) -> StdResult<bool> {
+        Ok(self.metadata_frozen.may_load(storage)?.unwrap_or_default())
+    }
+
+    pub fn is_soulbound(&self, storage: &dyn Storage, token_id: &str) -> StdResult<bool> {
+        Ok(self.contract_info.load(storage)?.soulbound
+            || self.soulbound_tokens.has(storage, token_id))
+    }
+
+    /// Appends to the token history and prunes the records beyond `provenance_limit`
+    pub fn record_provenance(
+        &self,
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        token_id: &str,
+        kind: ProvenanceKind,
+        from: Option<Addr>,
+        to: Addr,
+    ) -> StdResult<()> {
+        let limit = self
+            .provenance_limit
+            .may_load(storage)?
+            .unwrap_or(DEFAULT_PROVENANCE_LIMIT) as u64;
+        if limit == 0 {
+            return Ok(());
+        }
+
+        let history = self.provenance.prefix(token_id);
+        let first = history
+            .keys(storage, None, None, Order::Ascending)
+            .next()
+            .transpose()?;
+        let index = history
+            .keys(storage, None, None, Order::Descending)
+            .next()
+            .transpose()?
+            .map_or(0, |last| last + 1);
+
+        self.provenance.save(
+            storage,
+            (token_id, index),
+            &Provenance {
+                index,
+                kind,
+                from,
+                to,
+                height: block.height,
+                time: block.time,
+            },
+        )?;
+
+        // indexes are contiguous, so everything below `index + 1 - limit` is over the limit
+        if let Some(first) = first {
+            for old in first..(index + 1).saturating_sub(limit) {
+                self.provenance.remove(storage, (token_id, old));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Index and terms of the phase running at `time`
+    pub fn current_phase(
+        &self,
+        storage: &dyn Storage,
+        time: Timestamp,
+    ) -> StdResult<Option<(u32, MintPhase)>> {
+        let phases = self.phases.may_load(storage)?.unwrap_or_default();
+
+        Ok(phases
+            .into_iter()
+            .enumerate()
+            .find(|(_, phase)| phase.start_time <= time && time < phase.end_time)
+            .map(|(index, phase)| (index as u32, phase)))
+    }
+
+    pub fn has_role(&self, storage: &dyn Storage, role: Role, addr: &Addr) -> bool {
+        self.roles.has(storage, (role.as_str(), addr))
+    }
+
+    pub fn save_role(&self, storage: &mut dyn Storage, role: Role, addr: &Addr) -> StdResult<()> {
+        self.roles.save(storage, (role.as_str(), addr), &())
+    }
+
+    pub fn remove_role(&self, storage: &mut dyn Storage, role: Role, addr: &Addr) {
+        self.roles.remove(storage, (role.as_str(), addr))
+    }
+
+    pub fn clear_provenance(&self, storage: &mut dyn Storage, token_id: &str) -> StdResult<()> {
+        let indexes: Vec<u64> = self
+            .provenance
+            .prefix(token_id)
+            .keys(storage, None, None, Order::Ascending)
+            .collect::<StdResult<_>>()?;
+        for index in indexes {
+            self.provenance.remove(storage, (token_id, index));
+        }
+
+        Ok(())
+    }
+
+    pub fn decrement_tokens(&self, storage: &mut dyn Storage) -> StdResult<u64> {
+        let count = self.token_count(storage)?;
+        let count = count.checked_sub(1).ok_or_else(|| {
+            StdError::overflow(OverflowError::new(OverflowOperation::Sub, count, 1))
+        })?;
+        self.token_count.save(storage, &count)?;
+
+        Ok(count)
+    }
+}
+
+impl<'a, T> Contract<'a, T>
+where
+    T: Serialize + DeserializeOwned + Clone,
+{
     pub fn get_contract() -> Self {
         let indexes = TokenIndex {
-            owner: MultiIndex::new(|_, d: &Token| d.owner.clone(), TOKENS_PK, "tokens__owner"),
+            owner: MultiIndex::new(
+                |_, d: &Token<T>| d.owner.clone(),
+                TOKENS_PK,
+                "tokens__owner",
+            ),
         };
 
         Self {
-            contract_info: Item::new("contract_info"),
+            contract_info: Item::new("nft_info"),
             minter: Item::new("minter"),
+            roles: Map::new("roles"),
+            default_royalty: Item::new("default_royalty"),
             owner: Item::new("owner"),
+            pending_owner: Item::new("pending_owner"),
             tokens: IndexedMap::new(TOKENS_PK, indexes),
+            token_count: Item::new("num_tokens"),
+            next_token_id: Item::new("next_token_id"),
+            operators: Map::new("operators"),
+            batch_receivers: Map::new("batch_receivers"),
+            frozen_tokens: Map::new("frozen_tokens"),
+            metadata_frozen: Item::new("metadata_frozen"),
+            soulbound_tokens: Map::new("soulbound_tokens"),
+            paused: Item::new("paused"),
+            provenance: Map::new("provenance"),
+            provenance_limit: Item::new("provenance_limit"),
+            token_id_rules: Item::new("token_id_rules"),
+            sale: Item::new("sale"),
+            merkle_root: Item::new("merkle_root"),
+            allowlist_minted: Map::new("root_minted"),
+            phases: Item::new("mint_phases"),
+            phase_minted: Map::new("phase_minted"),
         }
     }
 }
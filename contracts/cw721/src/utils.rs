@@ -22,15 +22,21 @@ pub mod test_utils {
         Contract<'static>,
         Env,
         Response,
+    ) {
+        initialize_contract_with_msg(get_default_instantiate_msg())
+    }
+
+    pub fn initialize_contract_with_msg(
+        init_msg: InstantiateMsg,
+    ) -> (
+        OwnedDeps<MemoryStorage, MockApi, MockQuerier>,
+        Contract<'static>,
+        Env,
+        Response,
     ) {
         let mut deps = mock_dependencies();
 
         let contract = Contract::get_contract();
-        let init_msg = InstantiateMsg {
-            minter: MINTER.to_string(),
-            name: NAME.to_string(),
-            symbol: SYMBOL.to_string(),
-        };
         let env = mock_env();
 
         let info = get_mock_info(OWNER);
@@ -41,6 +47,21 @@ pub mod test_utils {
         (deps, contract, env, init_result)
     }
 
+    pub fn get_default_instantiate_msg() -> InstantiateMsg {
+        InstantiateMsg {
+            minter: MINTER.to_string(),
+            name: NAME.to_string(),
+            symbol: SYMBOL.to_string(),
+            royalty: None,
+            auto_token_id: false,
+            max_supply: None,
+            soulbound: false,
+            provenance_limit: None,
+            token_id_rules: None,
+            sale: None,
+        }
+    }
+
     pub fn get_mock_info(sender: &str) -> MessageInfo {
         mock_info(sender, &[])
     }
@@ -61,8 +82,11 @@ pub mod test_utils {
         ExecuteMsg::Mint {
             token: TokenMsg {
                 owner: OWNER.to_string(),
-                token_id: token_id.to_string(),
+                token_id: Some(token_id.to_string()),
                 token_uri: None,
+                royalty: None,
+                extension: None,
+                soulbound: false,
             },
         }
     }
@@ -19,4 +19,10 @@ pub enum ContractError {
     InvalidReply {
         msg: Option<String>
     },
+
+    #[error("Wrong contract, expected {expected}, found {found}")]
+    WrongContract { expected: String, found: String },
+
+    #[error("Cannot downgrade from {previous} to {current}")]
+    CannotDowngrade { previous: String, current: String },
 }
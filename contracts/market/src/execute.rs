@@ -3,18 +3,20 @@ use cosmwasm_std::{
     StdError, StdResult, SubMsg, SubMsgResponse, WasmMsg,
 };
 use cw2::set_contract_version;
+use cw_events::ActionEvent;
+use cw_utils::parse_reply_execute_data;
 
 use crate::{
     msg::{
-        Cw721ReceiveMsg, ExecuteMsg, ExecuteMsgCw721, InstantiateMsg, ReceiveLazyNftMsg, SaleData,
-        TokenMsg,
+        CollectionConfigResponse, Cw721ReceiveMsg, ExecuteMsg, ExecuteMsgCw721, InstantiateMsg,
+        MintResponse, QueryMsgCw721, ReceiveLazyNftMsg, SaleData, TokenMsg,
     },
     state::{Contract, LazyNft, Sale, TEMP, Temp},
     ContractError,
 };
 
-const CONTRACT_NAME: &str = "contract";
-const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+pub const CONTRACT_NAME: &str = "contract";
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 const MINT_RESPONSE_ID: u64 = 1;
 
 impl<'a> Contract<'a> {
@@ -75,12 +77,12 @@ impl<'a> Contract<'a> {
                 None => Ok(sale),
             })?;
 
-        Ok(Response::new()
-            .add_attribute("action", "receive")
-            .add_attribute("token_id", _msg.token_id)
-            .add_attribute("owner", _msg.sender)
-            .add_attribute("buy_token", data.price.denom)
-            .add_attribute("buy_price", data.price.amount))
+        Ok(ActionEvent::new("receive_nft")
+            .sender(_msg.sender)
+            .collection(_info.sender)
+            .token_id(_msg.token_id)
+            .price(&data.price)
+            .into_response())
     }
 
     pub fn receive_lazy_nft(
@@ -101,7 +103,11 @@ impl<'a> Contract<'a> {
                 None => Ok(lazy_nft),
             })?;
 
-        Ok(Response::default())
+        Ok(ActionEvent::new("receive_lazy_nft")
+            .sender(_info.sender)
+            .collection(_msg.contract)
+            .token_id(_msg.token_id)
+            .into_response())
     }
 
     pub fn remove_sale(
@@ -119,9 +125,11 @@ impl<'a> Contract<'a> {
 
         self.sales.remove(_deps.storage, &token_id)?;
 
-        Ok(Response::new()
-            .add_attribute("action", "remove_sale")
-            .add_attribute("token_id", sale.token_id))
+        Ok(ActionEvent::new("remove_sale")
+            .sender(_info.sender)
+            .collection(sale.contract)
+            .token_id(sale.token_id)
+            .into_response())
     }
 
     pub fn purchase(
@@ -149,7 +157,7 @@ impl<'a> Contract<'a> {
         self.sales.remove(_deps.storage, &token_id)?;
 
         let funds_transfer: CosmosMsg = BankMsg::Send {
-            to_address: sale.owner.into_string(),
+            to_address: sale.owner.to_string(),
             amount: vec![coin.clone()],
         }
         .into();
@@ -159,19 +167,21 @@ impl<'a> Contract<'a> {
             token_id: token_id.clone(),
         };
         let transfer_msg: CosmosMsg = WasmMsg::Execute {
-            contract_addr: sale.contract.into(),
+            contract_addr: sale.contract.to_string(),
             msg: to_binary(&transfer)?,
             funds: vec![],
         }
         .into();
 
-        Ok(Response::new()
-            .add_messages(vec![funds_transfer, transfer_msg])
-            .add_attribute("action", "purchase")
-            .add_attribute("buyer", _info.sender.into_string())
-            .add_attribute("token_id", token_id)
-            .add_attribute("buy_token", coin.denom)
-            .add_attribute("buy_price", coin.amount))
+        Ok(ActionEvent::new("purchase")
+            .sender(_info.sender.clone())
+            .recipient(_info.sender)
+            .collection(sale.contract)
+            .token_id(token_id)
+            .price(&coin)
+            .add_attribute("seller", sale.owner)
+            .into_response()
+            .add_messages(vec![funds_transfer, transfer_msg]))
     }
 
     pub fn purchase_lazy(
@@ -183,16 +193,26 @@ impl<'a> Contract<'a> {
     ) -> Result<Response, ContractError> {
         let lazy_nft = self.lazy_sales.load(_deps.storage, &token_id)?;
 
+        // collections that assign ids reject an explicit one, those without
+        // the `CollectionConfig` query always take the listed id
+        let auto_token_id = _deps
+            .querier
+            .query_wasm_smart::<CollectionConfigResponse>(
+                &lazy_nft.contract,
+                &QueryMsgCw721::CollectionConfig {},
+            )
+            .map(|config| config.auto_token_id)
+            .unwrap_or(false);
         let mint = ExecuteMsgCw721::Mint {
             token: TokenMsg {
-                owner: _info.sender.into_string(),
-                token_id: token_id.clone(),
+                owner: _info.sender.to_string(),
+                token_id: (!auto_token_id).then(|| token_id.clone()),
                 token_uri: None,
             },
         };
         let mint_msg = SubMsg::reply_on_success(
             WasmMsg::Execute {
-                contract_addr: lazy_nft.contract.into_string(),
+                contract_addr: lazy_nft.contract.to_string(),
                 msg: to_binary(&mint)?,
                 funds: vec![],
             },
@@ -204,7 +224,14 @@ impl<'a> Contract<'a> {
         };
         TEMP.save(_deps.storage, &temp)?;
 
-        Ok(Response::new().add_submessage(mint_msg))
+        Ok(ActionEvent::new("purchase_lazy")
+            .sender(_info.sender.clone())
+            .recipient(_info.sender)
+            .collection(lazy_nft.contract)
+            .token_id(token_id)
+            .price(&temp.funds)
+            .into_response()
+            .add_submessage(mint_msg))
     }
 }
 
@@ -217,31 +244,218 @@ impl<'a> Contract<'a> {
     }
 
     pub fn mint_reply(&self, _deps: DepsMut, _env: Env, _reply: Reply) -> StdResult<Response> {
+        let data = parse_reply_execute_data(_reply.clone())
+            .ok()
+            .and_then(|res| res.data);
         let res: SubMsgResponse = _reply.result.into_result().map_err(StdError::generic_err)?;
 
+        // cw721 returns the minted id as response data, older versions only emit it as an attribute
+        let token_id = match data {
+            Some(data) => from_binary::<MintResponse>(&data)?.token_id,
+            None => Self::minted_token_id(&res)?,
+        };
+
+        let temp = TEMP.load(_deps.storage)?;
+        TEMP.remove(_deps.storage);
+
+        Ok(ActionEvent::new("lazy_mint")
+            .token_id(token_id)
+            .price(&temp.funds)
+            .into_response())
+    }
+
+    fn minted_token_id(res: &SubMsgResponse) -> StdResult<String> {
         let mint_event = res
             .events
             .iter()
             .find(|event| {
-                event
-                    .attributes
-                    .iter()
-                    .any(|attr| attr.key == "action" && attr.value == "mint")
+                event.ty == "wasm-mint"
+                    || event
+                        .attributes
+                        .iter()
+                        .any(|attr| attr.key == "action" && attr.value == "mint")
             })
             .ok_or_else(|| StdError::generic_err("wrong event"))?;
 
-        let minted_token_attr = mint_event
+        mint_event
             .attributes
             .iter()
-            .cloned()
             .find(|attr| attr.key == "token_id")
-            .ok_or_else(|| StdError::generic_err("no token_id"))?;
-            
-        let temp = TEMP.load(_deps.storage)?;
-        TEMP.remove(_deps.storage);
+            .map(|attr| attr.value.clone())
+            .ok_or_else(|| StdError::generic_err("no token_id"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{
+        coin, coins, from_binary, to_binary, Binary, ContractResult, CosmosMsg, DepsMut, Event,
+        Reply, SubMsgResponse, SubMsgResult, SystemError, SystemResult, WasmMsg, WasmQuery,
+        testing::{mock_dependencies, mock_env, mock_info},
+    };
+
+    use crate::{
+        msg::{
+            CollectionConfigResponse, Cw721ReceiveMsg, ExecuteMsg, ExecuteMsgCw721, InstantiateMsg,
+            MintResponse, ReceiveLazyNftMsg, SaleData,
+        },
+        state::{Contract, TEMP, Temp},
+    };
+
+    use super::MINT_RESPONSE_ID;
+
+    const OWNER: &str = "owner";
+    const SELLER: &str = "seller";
+    const BUYER: &str = "buyer";
+    const COLLECTION: &str = "collection";
+    const TOKEN_ID: &str = "1";
+
+    #[test]
+    fn should_emit_purchase_event() {
+        let mut deps = mock_dependencies();
+        let contract = Contract::get_contract();
+        contract
+            .instantiate(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), InstantiateMsg {})
+            .unwrap();
+
+        let receive_msg = Cw721ReceiveMsg {
+            token_id: TOKEN_ID.to_string(),
+            sender: SELLER.to_string(),
+            msg: to_binary(&SaleData { price: coin(100, "umlg") }).unwrap(),
+        };
+        contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(COLLECTION, &[]),
+                ExecuteMsg::ReceiveNft(receive_msg),
+            )
+            .unwrap();
+
+        let response = contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(BUYER, &coins(100, "umlg")),
+                ExecuteMsg::Purchase { token_id: TOKEN_ID.to_string() },
+            )
+            .unwrap();
+
+        insta::assert_json_snapshot!(response);
+        assert!(contract.sales.may_load(&deps.storage, TOKEN_ID).unwrap().is_none());
+    }
+
+    fn list_lazy(contract: &Contract, mut deps: DepsMut) {
+        contract
+            .instantiate(deps.branch(), mock_env(), mock_info(OWNER, &[]), InstantiateMsg {})
+            .unwrap();
+        contract
+            .execute(
+                deps,
+                mock_env(),
+                mock_info(SELLER, &[]),
+                ExecuteMsg::ReceiveLazyNft(ReceiveLazyNftMsg {
+                    token_id: TOKEN_ID.to_string(),
+                    contract: COLLECTION.to_string(),
+                }),
+            )
+            .unwrap();
+    }
+
+    fn minted_token_id(contract: &Contract, deps: DepsMut) -> Option<String> {
+        let response = contract
+            .execute(
+                deps,
+                mock_env(),
+                mock_info(BUYER, &coins(100, "umlg")),
+                ExecuteMsg::PurchaseLazy { token_id: TOKEN_ID.to_string() },
+            )
+            .unwrap();
+        match &response.messages[0].msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => match from_binary(msg).unwrap() {
+                ExecuteMsgCw721::Mint { token } => token.token_id,
+                _ => panic!("expected a mint"),
+            },
+            _ => panic!("expected a wasm message"),
+        }
+    }
+
+    fn reply(contract: &Contract, deps: DepsMut, events: Vec<Event>, data: Option<Binary>) -> String {
+        TEMP.save(deps.storage, &Temp { funds: coin(100, "umlg") }).unwrap();
+        let reply = Reply {
+            id: MINT_RESPONSE_ID,
+            result: SubMsgResult::Ok(SubMsgResponse { events, data }),
+        };
+        let response = contract.reply(deps, mock_env(), reply).unwrap();
+        response.events[0]
+            .attributes
+            .iter()
+            .find(|attr| attr.key == "token_id")
+            .unwrap()
+            .value
+            .clone()
+    }
+
+    #[test]
+    fn should_mint_lazy_nft_without_id_on_auto_id_collection() {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { .. } => SystemResult::Ok(ContractResult::Ok(
+                to_binary(&CollectionConfigResponse {
+                    auto_token_id: true,
+                    max_supply: None,
+                    soulbound: false,
+                    metadata_frozen: false,
+                })
+                .unwrap(),
+            )),
+            _ => unimplemented!(),
+        });
+        let contract = Contract::get_contract();
+        list_lazy(&contract, deps.as_mut());
+
+        assert_eq!(minted_token_id(&contract, deps.as_mut()), None);
+    }
+
+    #[test]
+    fn should_mint_lazy_nft_with_listed_id() {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { contract_addr, .. } => SystemResult::Err(SystemError::NoSuchContract {
+                addr: contract_addr.clone(),
+            }),
+            _ => unimplemented!(),
+        });
+        let contract = Contract::get_contract();
+        list_lazy(&contract, deps.as_mut());
+
+        assert_eq!(minted_token_id(&contract, deps.as_mut()), Some(TOKEN_ID.to_string()));
+    }
+
+    #[test]
+    fn should_read_minted_id_from_reply_data() {
+        let mut deps = mock_dependencies();
+        let contract = Contract::get_contract();
+        // MsgExecuteContractResponse with the MintResponse as its data field
+        let mint_response = to_binary(&MintResponse { token_id: "7".to_string() }).unwrap();
+        let mut data = vec![0x0a, mint_response.len() as u8];
+        data.extend_from_slice(&mint_response);
+
+        let token_id = reply(&contract, deps.as_mut(), vec![], Some(data.into()));
+
+        assert_eq!(token_id, "7");
+        assert!(TEMP.may_load(&deps.storage).unwrap().is_none());
+    }
+
+    #[test]
+    fn should_read_minted_id_from_legacy_event() {
+        let mut deps = mock_dependencies();
+        let contract = Contract::get_contract();
+        let events = vec![Event::new("wasm-mint").add_attribute("token_id", "7")];
 
-        let funds = format!("{}:{}",temp.funds.denom, temp.funds.amount);
+        let token_id = reply(&contract, deps.as_mut(), events, None);
 
-        Ok(Response::new().add_attribute(minted_token_attr.key, minted_token_attr.value).add_attribute("attached_funds", funds))
+        assert_eq!(token_id, "7");
+        assert!(TEMP.may_load(&deps.storage).unwrap().is_none());
     }
 }
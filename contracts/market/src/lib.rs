@@ -1,6 +1,7 @@
 pub mod contract;
 mod error;
 pub mod execute;
+pub mod migrate;
 pub mod msg;
 pub mod query;
 pub mod state;
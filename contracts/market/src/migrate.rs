@@ -0,0 +1,141 @@
+use cosmwasm_std::{DepsMut, Env, Response, StdError};
+use cw2::{get_contract_version, set_contract_version};
+use cw_events::ActionEvent;
+use semver::Version;
+
+use crate::{
+    execute::{CONTRACT_NAME, CONTRACT_VERSION},
+    msg::MigrateMsg,
+    state::Contract,
+    ContractError,
+};
+
+impl<'a> Contract<'a> {
+    pub fn migrate(
+        &self,
+        mut _deps: DepsMut,
+        _env: Env,
+        _msg: MigrateMsg,
+    ) -> Result<Response, ContractError> {
+        let stored = get_contract_version(_deps.storage)?;
+
+        if stored.contract != CONTRACT_NAME {
+            return Err(ContractError::WrongContract {
+                expected: CONTRACT_NAME.to_string(),
+                found: stored.contract,
+            });
+        }
+
+        let previous = parse_version(&stored.version)?;
+        let current = parse_version(CONTRACT_VERSION)?;
+
+        if previous > current {
+            return Err(ContractError::CannotDowngrade {
+                previous: stored.version,
+                current: CONTRACT_VERSION.to_string(),
+            });
+        }
+
+        self.migrate_state(_deps.branch(), &previous)?;
+        set_contract_version(_deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+        Ok(ActionEvent::new("migrate")
+            .add_attribute("from_version", stored.version)
+            .add_attribute("to_version", CONTRACT_VERSION)
+            .into_response())
+    }
+
+    /// Brings `sales` and `lazy_sales` written by `previous` up to the current layout.
+    /// Every layout change gets its own `if *previous < Version::new(..)` step.
+    pub fn migrate_state(&self, _deps: DepsMut, _previous: &Version) -> Result<(), ContractError> {
+        Ok(())
+    }
+}
+
+fn parse_version(version: &str) -> Result<Version, ContractError> {
+    Version::parse(version).map_err(|err| StdError::generic_err(err.to_string()).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{
+        coin,
+        testing::{mock_dependencies, mock_env, mock_info},
+        Addr,
+    };
+    use cw2::{get_contract_version, set_contract_version};
+
+    use crate::{
+        execute::{CONTRACT_NAME, CONTRACT_VERSION},
+        msg::{InstantiateMsg, MigrateMsg},
+        state::{Contract, LazyNft, Sale},
+        ContractError,
+    };
+
+    const OWNER: &str = "owner";
+    const COLLECTION: &str = "collection";
+
+    #[test]
+    fn should_fail_migrate_from_another_contract() {
+        let mut deps = mock_dependencies();
+        let contract = Contract::get_contract();
+        contract
+            .instantiate(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), InstantiateMsg {})
+            .unwrap();
+        set_contract_version(&mut deps.storage, "crates.io:another", CONTRACT_VERSION).unwrap();
+
+        let migrate_result = contract
+            .migrate(deps.as_mut(), mock_env(), MigrateMsg {})
+            .unwrap_err();
+
+        assert!(matches!(migrate_result, ContractError::WrongContract { .. }))
+    }
+
+    #[test]
+    fn should_fail_migrate_to_older_version() {
+        let mut deps = mock_dependencies();
+        let contract = Contract::get_contract();
+        contract
+            .instantiate(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), InstantiateMsg {})
+            .unwrap();
+        set_contract_version(&mut deps.storage, CONTRACT_NAME, "999.0.0").unwrap();
+
+        let migrate_result = contract
+            .migrate(deps.as_mut(), mock_env(), MigrateMsg {})
+            .unwrap_err();
+
+        assert!(matches!(migrate_result, ContractError::CannotDowngrade { .. }))
+    }
+
+    #[test]
+    fn should_migrate_and_keep_sales() {
+        let mut deps = mock_dependencies();
+        let contract = Contract::get_contract();
+        contract
+            .instantiate(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), InstantiateMsg {})
+            .unwrap();
+        set_contract_version(&mut deps.storage, CONTRACT_NAME, "0.0.1").unwrap();
+
+        let sale = Sale {
+            token_id: "1".to_string(),
+            owner: Addr::unchecked(OWNER),
+            contract: Addr::unchecked(COLLECTION),
+            price: coin(100, "umlg"),
+        };
+        let lazy_nft = LazyNft {
+            token_id: "2".to_string(),
+            contract: Addr::unchecked(COLLECTION),
+        };
+        contract.sales.save(&mut deps.storage, "1", &sale).unwrap();
+        contract.lazy_sales.save(&mut deps.storage, "2", &lazy_nft).unwrap();
+
+        contract
+            .migrate(deps.as_mut(), mock_env(), MigrateMsg {})
+            .unwrap();
+
+        let version = get_contract_version(&deps.storage).unwrap();
+        assert_eq!(version.version, CONTRACT_VERSION);
+        assert_eq!(contract.sales.load(&deps.storage, "1").unwrap(), sale);
+        assert_eq!(contract.lazy_sales.load(&deps.storage, "2").unwrap(), lazy_nft);
+    }
+}
@@ -6,6 +6,9 @@ use crate::state::Sale;
 #[cw_serde]
 pub struct InstantiateMsg {}
 
+#[cw_serde]
+pub struct MigrateMsg {}
+
 #[cw_serde]
 pub struct Cw721ReceiveMsg {
     pub token_id: String,
@@ -47,10 +50,31 @@ pub enum ExecuteMsgCw721 {
 #[cw_serde]
 pub struct TokenMsg {
     pub owner: String,
-    pub token_id: String,
+    /// Must be empty when the collection assigns ids itself
+    pub token_id: Option<String>,
     pub token_uri: Option<String>,
 }
 
+#[cw_serde]
+pub enum QueryMsgCw721 {
+    CollectionConfig {},
+}
+
+/// Response of the cw721 `CollectionConfig` query
+#[cw_serde]
+pub struct CollectionConfigResponse {
+    pub auto_token_id: bool,
+    pub max_supply: Option<u64>,
+    pub soulbound: bool,
+    pub metadata_frozen: bool,
+}
+
+/// Response data of the cw721 `Mint`
+#[cw_serde]
+pub struct MintResponse {
+    pub token_id: String,
+}
+
 #[cw_serde]
 pub struct SalesResponse {
     pub sales: Vec<Sale>,
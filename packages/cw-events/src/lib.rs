@@ -0,0 +1,84 @@
+//! Event schema shared by every handler.
+//!
+//! Each action emits a typed event, reported by the chain as `wasm-<action>`, and keeps
+//! the `action` attribute on the default `wasm` event. Common keys mean the same in
+//! every event and come first, action specific keys follow them:
+//!
+//! - `sender`: address that sent the message
+//! - `recipient`: address the token, or the handed over role, goes to
+//! - `collection`: cw721 contract of the token, set by contracts other than the collection
+//! - `token_id`: id of the token
+//! - `price`: amount paid for the token, e.g. `100umlg`
+
+use cosmwasm_std::{Coin, Event, Response};
+
+pub struct ActionEvent {
+    action: &'static str,
+    event: Event,
+}
+
+impl ActionEvent {
+    pub fn new(action: &'static str) -> Self {
+        Self {
+            action,
+            event: Event::new(action),
+        }
+    }
+
+    pub fn sender(self, sender: impl Into<String>) -> Self {
+        self.add_attribute("sender", sender)
+    }
+
+    pub fn recipient(self, recipient: impl Into<String>) -> Self {
+        self.add_attribute("recipient", recipient)
+    }
+
+    pub fn collection(self, collection: impl Into<String>) -> Self {
+        self.add_attribute("collection", collection)
+    }
+
+    pub fn token_id(self, token_id: impl Into<String>) -> Self {
+        self.add_attribute("token_id", token_id)
+    }
+
+    pub fn price(self, price: &Coin) -> Self {
+        self.add_attribute("price", price.to_string())
+    }
+
+    pub fn add_attribute(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.event = self.event.add_attribute(key, value);
+        self
+    }
+
+    /// Response with the `action` attribute and this event
+    pub fn into_response(self) -> Response {
+        Response::new()
+            .add_attribute("action", self.action)
+            .add_event(self.event)
+    }
+}
+
+impl From<ActionEvent> for Event {
+    fn from(action_event: ActionEvent) -> Self {
+        action_event.event
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::coin;
+
+    use super::ActionEvent;
+
+    #[test]
+    fn should_build_response_with_typed_event() {
+        let response = ActionEvent::new("public_mint")
+            .sender("minter")
+            .recipient("owner")
+            .price(&coin(200, "umlg"))
+            .add_attribute("count", "2")
+            .into_response();
+
+        insta::assert_json_snapshot!(response);
+    }
+}
@@ -1,5 +1,5 @@
 import { SigningCosmWasmClient } from "@cosmjs/cosmwasm-stargate";
-import { Token } from "./types";
+import { Token, TokensInfoResponse } from "./types";
 
 export class Cw721Contract {
   contractAddress: string;
@@ -34,10 +34,17 @@ export class Cw721Contract {
   }
 
   async getOwnedTokens(ownerAddress: string): Promise<Array<Token>> {
-    const ownedTokens = await this.signingClient.queryContractSmart(
-      this.contractAddress,
-      { tokens: { owner: ownerAddress } }
-    );
+    const ownedTokens: Token[] = [];
+    let startAfter: string | undefined;
+
+    do {
+      const page: TokensInfoResponse =
+        await this.signingClient.queryContractSmart(this.contractAddress, {
+          tokens_info: { owner: ownerAddress, start_after: startAfter },
+        });
+      ownedTokens.push(...page.tokens);
+      startAfter = page.next_start_after ?? undefined;
+    } while (startAfter);
 
     return ownedTokens;
   }
@@ -3,3 +3,8 @@ export type Token = {
   token_id: string;
   token_uri: string;
 };
+
+export type TokensInfoResponse = {
+  tokens: Token[];
+  next_start_after: string | null;
+};
@@ -83,20 +83,33 @@ describe("cw721", () => {
   });
 
   it("should proceed querying tokens", async () => {
-    const returnValue: Token[] = [
+    const firstPage: Token[] = [
       { owner: MINTER, token_id: TOKEN_ID, token_uri: TOKEN_URI },
       { owner: MINTER, token_id: "2", token_uri: "ipfs54321" },
     ];
-    signingClientMock.queryContractSmart.mockResolvedValue(returnValue);
+    const secondPage: Token[] = [
+      { owner: MINTER, token_id: "3", token_uri: "ipfs67890" },
+    ];
+    signingClientMock.queryContractSmart
+      .mockResolvedValueOnce({ tokens: firstPage, next_start_after: "2" })
+      .mockResolvedValueOnce({ tokens: secondPage, next_start_after: null });
 
     const tokens = await cw721.getOwnedTokens(MINTER);
 
-    expect(signingClientMock.queryContractSmart).toHaveBeenCalledWith(
+    expect(signingClientMock.queryContractSmart).toHaveBeenNthCalledWith(
+      1,
+      CONTRACT_ADDRESS,
+      {
+        tokens_info: { owner: MINTER, start_after: undefined },
+      }
+    );
+    expect(signingClientMock.queryContractSmart).toHaveBeenNthCalledWith(
+      2,
       CONTRACT_ADDRESS,
       {
-        tokens: { owner: MINTER },
+        tokens_info: { owner: MINTER, start_after: "2" },
       }
     );
-    expect(tokens).toEqual(returnValue);
+    expect(tokens).toEqual([...firstPage, ...secondPage]);
   });
 });